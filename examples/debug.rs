@@ -1,4 +1,5 @@
 extern crate hyperbolic;
+use hyperbolic::draw::NannouDraw;
 use hyperbolic::tiling::Tiling;
 use nannou::prelude::*;
 
@@ -28,16 +29,20 @@ fn view(app: &App, model: &Model, frame: Frame) {
     // Clear the background to blue.
     draw.background().color(WHITE);
     let mut counter = 0;
-    for shape in model.tiling.shapes().unwrap() {
+    let polygon = model.tiling.polygon().unwrap();
+    for tile in model.tiling.tiles().unwrap() {
         counter += 1;
         if counter >= model.counter {
             break;
         }
+        let shape = tile.vertices(polygon);
         for i in 0..shape.len() {
             let a = shape[i];
             let b = shape[(i + 1) % shape.len()];
             if let Some(geodesic) =
-                hyperbolic::geodesic_passing_by_two_points(a, b)
+                hyperbolic::geometry::geodesic_passing_by_two_points(
+                    a, b,
+                )
             {
                 geodesic.draw(&draw);
             }
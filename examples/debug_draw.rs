@@ -1,4 +1,5 @@
 extern crate hyperbolic;
+use hyperbolic::draw::NannouDraw;
 use hyperbolic::tiling::Tiling;
 use nannou::prelude::*;
 
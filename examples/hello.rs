@@ -1,16 +1,14 @@
 extern crate hyperbolic;
+use hyperbolic::draw::NannouDraw;
 use hyperbolic::tiling::Tiling;
 use nannou::prelude::*;
 use nannou_egui::*;
 
 const MAX_P: u8 = 8;
 const MAX_Q: u8 = 8;
-const P3_MAX_DEPTH: u8 = 10;
-const P4_MAX_DEPTH: u8 = 7;
-const P5_6_MAX_DEPTH: u8 = 6;
-const P7_8_MAX_DEPTH: u8 = 4;
-const DEFAULT_MAX_DEPTH: u8 = 3;
+const MAX_DEPTH: u8 = 12;
 const POINCARE_RADIUS: u8 = 1;
+const SCALE: f32 = 200f32;
 
 fn main() {
     nannou::app(model).update(update).run()
@@ -46,6 +44,8 @@ fn model(app: &App) -> Model {
     let egui = Egui::from_window(&app.main_window());
     let mut tiling =
         Tiling::new(settings.p, settings.q, settings.max_depth);
+    // On ne calcule pas les polygones plus petits qu'un pixel.
+    tiling.set_min_diameter(Some(1f64 / SCALE as f64));
     tiling.compute();
     Model {
         tiling,
@@ -72,22 +72,13 @@ fn update(_app: &App, model: &mut Model, _update: Update) {
                     .text("Polygones rencontrés en un point"),
             )
             .changed();
-        // Permets une valeur de profondeur de récursion raisonnable
-        // et adaptée à p, afin d'éviter les ralentissements.
-        let max_depth_permitted = match model.settings.p {
-            3 => P3_MAX_DEPTH,
-            4 => P4_MAX_DEPTH,
-            5 | 6 => P5_6_MAX_DEPTH,
-            7 | 8 => P7_8_MAX_DEPTH,
-            _ => DEFAULT_MAX_DEPTH,
-        };
-        model.settings.max_depth =
-            model.settings.max_depth.clamp(1, max_depth_permitted);
+        // Les polygones plus petits qu'un pixel n'étant pas calculés,
+        // une grande profondeur n'entraîne pas de ralentissements.
         changed |= ui
             .add(
                 egui::Slider::new(
                     &mut model.settings.max_depth,
-                    1..=max_depth_permitted,
+                    1..=MAX_DEPTH,
                 )
                 .text("Profondeur maximale"),
             )
@@ -123,7 +114,7 @@ fn view(app: &App, model: &Model, frame: Frame) {
         return;
     }
     // On zoom sur notre dessin.
-    let draw = draw.scale(200f32);
+    let draw = draw.scale(SCALE);
 
     // On dessine le disque de Poincaré
     draw.ellipse()
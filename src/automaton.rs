@@ -0,0 +1,271 @@
+use std::collections::{HashMap, VecDeque};
+
+use glam::DVec2;
+
+use crate::geometry::hyperbolic_distance;
+use crate::key::PointMap;
+use crate::mobius::Mobius;
+use crate::tiling::Tiling;
+
+// Position d'un mot concurrent par rapport au mot lu, dans l'ordre
+// shortlex. Les variantes sont rangées par importance : un concurrent
+// plus petit peut faire tout ce que fait un concurrent plus grand
+// menant au même polygone, qui peut lui-même s'arrêter.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+enum Rival {
+    // Même longueur, plus petit dans l'ordre lexicographique.
+    Smaller,
+    // Même longueur, plus grand dans l'ordre lexicographique.
+    Greater,
+    // Plus court, et donc plus petit dans l'ordre shortlex.
+    Shorter,
+}
+
+// Un état est l'ensemble des concurrents du mot lu, chacun donné par
+// la position de son polygone par rapport à celui atteint par le mot.
+type State = Vec<(usize, Rival)>;
+
+// Automate fini reconnaissant exactement les mots des polygones d'un
+// pavage {p,q} (voir Tiling::word). Parcourir ses mots acceptés
+// couche par couche permet d'obtenir chaque polygone une seule fois,
+// sans chercher s'il a déjà été atteint. Sa construction, elle, est
+// géométrique : les polygones proches du polygone initial y sont
+// reconnus à leur centre par un PointMap. Deux centres distincts
+// étant distants d'au moins deux fois Tiling::inradius, cette
+// distance doit rester supérieure à la tolérance du PointMap.
+#[derive(Debug, Clone)]
+pub struct Automaton {
+    // État atteint depuis chaque état en lisant chaque lettre, s'il
+    // existe. L'état initial est l'état 0.
+    transitions: Vec<Vec<Option<usize>>>,
+}
+
+// Données utilisées durant la construction de l'automate.
+struct Builder<'a> {
+    reflections: &'a [Mobius],
+    // Distance au-delà de laquelle on oublie un concurrent.
+    bound: f64,
+    // Positions des polygones rencontrés, repérés par leur centre,
+    // et une isométrie envoyant le polygone initial sur chacun. La
+    // position 0 est celle du polygone initial.
+    positions: PointMap<usize>,
+    transforms: Vec<Mobius>,
+    // Positions atteintes depuis chaque position lorsque le mot lit
+    // chaque lettre, calculées à la demande (voir Builder::moves).
+    moves: Vec<Vec<Vec<Option<usize>>>>,
+}
+
+impl Builder<'_> {
+    // Retourne l'indice de la position du polygone image du polygone
+    // initial par une isométrie, s'il est assez proche.
+    fn position(&mut self, transform: Mobius) -> Option<usize> {
+        let center = transform.apply(DVec2::ZERO);
+        if hyperbolic_distance(DVec2::ZERO, center) > self.bound {
+            return None;
+        }
+        if let Some(&index) = self.positions.get(center) {
+            return Some(index);
+        }
+        let index = self.transforms.len();
+        self.positions.insert(center, index);
+        self.transforms.push(transform);
+        self.moves.push(vec![vec![]; self.reflections.len()]);
+        Some(index)
+    }
+    // Retourne les positions atteintes par un mot concurrent situé à
+    // une position donnée lorsque le mot lit une lettre : s'il prend
+    // chacune des lettres, puis s'il s'arrête. Seul le polygone du
+    // concurrent importe, et non l'isométrie qui l'y envoie.
+    fn moves(
+        &mut self,
+        position: usize,
+        letter: usize,
+    ) -> &[Option<usize>] {
+        if self.moves[position][letter].is_empty() {
+            let reflections = self.reflections;
+            // Le mot étant reflété, le concurrent est vu depuis le
+            // polygone voisin.
+            let transform = reflections[letter]
+                .compose(&self.transforms[position]);
+            let mut moves: Vec<Option<usize>> = reflections
+                .iter()
+                .map(|reflection| {
+                    self.position(transform.compose(reflection))
+                })
+                .collect();
+            moves.push(self.position(transform));
+            self.moves[position][letter] = moves;
+        }
+        &self.moves[position][letter]
+    }
+    // Retourne l'état atteint en lisant une lettre, ou rien si un
+    // concurrent plus petit atteint le même polygone que le mot.
+    fn next(
+        &mut self,
+        state: &State,
+        letter: usize,
+    ) -> Option<State> {
+        let letters = self.reflections.len();
+        let mut next = vec![];
+        // Le mot lu est son propre concurrent s'il prend une autre
+        // lettre ou s'arrête. Les autres concurrents peuvent prendre
+        // n'importe quelle lettre, leur position dans l'ordre étant
+        // déjà fixée, ou s'arrêter.
+        let rivals = std::iter::once((0, None)).chain(
+            state
+                .iter()
+                .map(|&(position, rival)| (position, Some(rival))),
+        );
+        for (position, rival) in rivals {
+            let moves = self.moves(position, letter);
+            for (other, &reached) in moves.iter().enumerate() {
+                let rival = match rival {
+                    _ if other == letters => Rival::Shorter,
+                    Some(Rival::Shorter) => continue,
+                    Some(rival) => rival,
+                    None if other < letter => Rival::Smaller,
+                    None if other > letter => Rival::Greater,
+                    None => continue,
+                };
+                match reached {
+                    Some(0) if rival != Rival::Greater => {
+                        return None
+                    }
+                    Some(position) => next.push((position, rival)),
+                    None => {}
+                }
+            }
+        }
+        // À position égale, on ne garde que le concurrent le plus
+        // important.
+        next.sort();
+        next.dedup_by_key(|&mut (position, _)| position);
+        Some(next)
+    }
+}
+
+impl Automaton {
+    // Construit l'automate d'un pavage, qui doit avoir été calculé.
+    // Les apeirogones ne sont pas pris en charge. Le nombre d'états
+    // croît vite avec p et q : {7,3} en a une centaine, mais {20,20}
+    // près de 80 000, pour une vingtaine de secondes de calcul.
+    pub fn new(tiling: &Tiling) -> Option<Self> {
+        if tiling.p().is_infinite() {
+            return None;
+        }
+        tiling.polygon()?;
+        // Un mot est accepté si aucun mot plus petit dans l'ordre
+        // shortlex ne mène au même polygone. On suit donc, en lisant
+        // le mot, tous les mots concurrents. Deux mots minimaux menant
+        // au même polygone restent proches l'un de l'autre : ils
+        // contournent au plus un sommet, ou un côté si q est infini
+        // (les côtés séparant alors le disque). On peut oublier les
+        // concurrents plus éloignés, ce qui rend l'automate fini.
+        let inradius = tiling.inradius()?;
+        let bound = if tiling.q().is_infinite() {
+            2f64 * inradius
+        } else {
+            2f64 * (inradius + tiling.circumradius()?)
+        };
+        let mut builder = Builder {
+            reflections: tiling.reflections(),
+            bound: bound + 1e-9,
+            positions: PointMap::new(),
+            transforms: vec![],
+            moves: vec![],
+        };
+        builder.position(Mobius::identity());
+        let mut states: Vec<State> = vec![vec![]];
+        let mut indices = HashMap::from([(vec![], 0)]);
+        let mut transitions = vec![];
+        let mut queue = VecDeque::from([0]);
+        while let Some(current) = queue.pop_front() {
+            let mut row = vec![];
+            for letter in 0..builder.reflections.len() {
+                let next = builder.next(&states[current], letter);
+                row.push(next.map(|next| match indices.get(&next) {
+                    Some(&index) => index,
+                    None => {
+                        let index = states.len();
+                        indices.insert(next.clone(), index);
+                        states.push(next);
+                        queue.push_back(index);
+                        index
+                    }
+                }));
+            }
+            transitions.push(row);
+        }
+        Some(Automaton { transitions })
+    }
+    // Retourne le nombre d'états de l'automate.
+    pub fn states(&self) -> usize {
+        self.transitions.len()
+    }
+    // Retourne l'état atteint en lisant une lettre (l'indice d'un
+    // côté) depuis un état, si le mot lu reste accepté.
+    pub fn next(&self, state: usize, letter: usize) -> Option<usize> {
+        *self.transitions.get(state)?.get(letter)?
+    }
+    // Vérifie si un mot est le mot d'un polygone.
+    pub fn accepts(&self, word: &[usize]) -> bool {
+        word.iter()
+            .try_fold(0, |state, &letter| self.next(state, letter))
+            .is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tiling::{Backend, Order};
+
+    fn compute(
+        p: u8,
+        q: Order,
+        max_depth: u8,
+        backend: Backend,
+    ) -> Tiling {
+        let mut tiling = Tiling::new(p, q, max_depth);
+        tiling.set_backend(backend);
+        tiling.compute();
+        tiling
+    }
+
+    // L'énumération par l'automate donne chaque polygone une seule
+    // fois sans chercher s'il a déjà été atteint : on doit retrouver
+    // exactement les couches et les mots du parcours géométrique.
+    #[test]
+    fn backends_agree() {
+        let cases = [
+            (7, 3.into(), 8),
+            (4, 5.into(), 7),
+            (5, 4.into(), 6),
+            (3, 7.into(), 10),
+            (6, 4.into(), 5),
+            (3, 8.into(), 10),
+            (3, Order::Infinite, 8),
+        ];
+        for (p, q, max_depth) in cases {
+            let geometric =
+                compute(p, q, max_depth, Backend::Geometric);
+            let automatic =
+                compute(p, q, max_depth, Backend::Automaton);
+            for layer in 0..=max_depth {
+                assert_eq!(
+                    geometric.layer(layer).len(),
+                    automatic.layer(layer).len(),
+                    "{{{p},{q}}}, couche {layer}"
+                );
+            }
+            let automaton = Automaton::new(&automatic).unwrap();
+            for id in 0..geometric.tiles().unwrap().len() {
+                let word = geometric.word(id);
+                assert_eq!(word, automatic.word(id), "{{{p},{q}}}");
+                assert!(automaton.accepts(&word.unwrap()));
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::quotient::{
+    classes, cosets, orbits, reflect_side, Mirror,
+};
+use crate::tiling::{TileId, Tiling};
+
+// Règle de coloriage d'un pavage {p,q}. Les sous-groupes du groupe de
+// symétries du pavage sont donnés par des générateurs : des mots dans
+// les réflexions par rapport aux côtés du triangle fondamental (voir
+// quotient::Mirror).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    // Damier : selon que l'isométrie du polygone renverse ou non
+    // l'orientation, c'est-à-dire selon la parité du nombre de
+    // réflexions menant au polygone. Si q est pair ou infini, deux
+    // voisins sont toujours de couleurs différentes. Sinon, aucun
+    // coloriage en deux couleurs ne le permet : les polygones entourant
+    // un sommet forment un cycle impair.
+    Checkerboard,
+    // Les symétries du sous-groupe préservent les couleurs : chaque
+    // couleur est une orbite de polygones sous son action.
+    Invariant(Vec<Vec<Mirror>>),
+    // Coloriage parfait : chaque symétrie du pavage permute les
+    // couleurs. Les couleurs sont les classes à gauche du sous-groupe
+    // engendré par les générateurs et par le stabilisateur du
+    // polygone initial (les réflexions AB et AC).
+    Perfect(Vec<Vec<Mirror>>),
+}
+
+// Coloriage d'un pavage calculé : un indice de couleur par polygone,
+// que n'importe quel moteur de rendu peut associer à une couleur.
+#[derive(Debug, Clone)]
+pub struct Colouring {
+    colours: Vec<usize>,
+    count: usize,
+}
+
+impl Colouring {
+    // Colorie un pavage calculé. On ne retourne rien si le pavage n'a
+    // pas été calculé, ou, pour les règles utilisant un sous-groupe,
+    // si ses polygones n'ont pas tous un mot (voir Tiling::dual), si
+    // le sous-groupe est d'indice infini ou trop grand, ou si p ou q
+    // est infini.
+    pub fn new(tiling: &Tiling, rule: &Rule) -> Option<Self> {
+        let tiles = tiling.tiles()?;
+        let (p, q) = (tiling.p().finite(), tiling.q().finite());
+        match rule {
+            Rule::Checkerboard => Some(Colouring {
+                colours: tiles
+                    .iter()
+                    .map(|tile| {
+                        tile.transform().is_reversing().into()
+                    })
+                    .collect(),
+                count: 2,
+            }),
+            Rule::Invariant(generators) => {
+                let table = cosets(p?, q?, &[], generators)?;
+                // Le polygone d'isométrie g est dans l'orbite de la
+                // classe Hg, à une symétrie du polygone initial près.
+                let (orbits, sizes) = orbits(&table, [0, 1]);
+                let classes = classes(tiling, &table)?;
+                Some(Colouring {
+                    colours: classes
+                        .into_iter()
+                        .map(|class| orbits[class])
+                        .collect(),
+                    count: sizes.len(),
+                })
+            }
+            Rule::Perfect(generators) => {
+                let mut generators = generators.clone();
+                generators.push(vec![Mirror::AB]);
+                generators.push(vec![Mirror::AC]);
+                let table = cosets(p?, q?, &[], &generators)?;
+                // La couleur du polygone d'isométrie g est la classe
+                // gK, identifiée à la classe à droite Kg⁻¹. Chaque
+                // réflexion étant une involution, g⁻¹ s'obtient en
+                // lisant le mot du polygone à l'envers.
+                let colours = (0..tiles.len())
+                    .map(|id| {
+                        tiling.word(id).map(|word| {
+                            word.iter().rev().fold(
+                                0,
+                                |class, &side| {
+                                    reflect_side(&table, class, side)
+                                },
+                            )
+                        })
+                    })
+                    .collect::<Option<_>>()?;
+                Some(Colouring {
+                    colours,
+                    count: table.len(),
+                })
+            }
+        }
+    }
+    // Retourne la couleur de chaque polygone, dans l'ordre de
+    // Tiling::tiles.
+    pub fn colours(&self) -> &Vec<usize> {
+        &self.colours
+    }
+    pub fn colour(&self, id: TileId) -> Option<usize> {
+        self.colours.get(id).copied()
+    }
+    // Retourne le nombre de couleurs. Toutes n'apparaissent pas
+    // forcément si le pavage est petit.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quotient::Mirror::*;
+
+    // Si q est pair, le damier donne toujours deux couleurs différentes
+    // à deux voisins, y compris sur le pavage dual.
+    #[test]
+    fn checkerboard_alternates() {
+        let mut tiling = Tiling::new(4, 6, 4);
+        tiling.compute();
+        let dual = tiling.dual().unwrap();
+        for tiling in [&tiling, &dual] {
+            let colouring =
+                Colouring::new(tiling, &Rule::Checkerboard).unwrap();
+            for id in 0..tiling.tiles().unwrap().len() {
+                for &(neighbour, _) in
+                    tiling.neighbours(id).unwrap().iter().flatten()
+                {
+                    assert_ne!(
+                        colouring.colour(id),
+                        colouring.colour(neighbour)
+                    );
+                }
+            }
+        }
+    }
+
+    // L'exemple du README colorie {6,4} en quatre couleurs.
+    #[test]
+    fn perfect_colouring_of_readme() {
+        let mut tiling = Tiling::new(6, 4, 5);
+        tiling.compute();
+        let rule = Rule::Perfect(vec![vec![BC, AB, AC, AB, BC]]);
+        let colouring = Colouring::new(&tiling, &rule).unwrap();
+        assert_eq!(colouring.count(), 4);
+        let mut used = colouring.colours().clone();
+        used.sort();
+        used.dedup();
+        assert_eq!(used, vec![0, 1, 2, 3]);
+    }
+}
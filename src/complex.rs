@@ -0,0 +1,92 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use glam::DVec2;
+
+// Nombre complexe. Un point du plan (x;y) est
+// représenté par le nombre x + iy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub const ZERO: Complex = Complex { re: 0f64, im: 0f64 };
+    pub const ONE: Complex = Complex { re: 1f64, im: 0f64 };
+
+    pub fn new(re: f64, im: f64) -> Self {
+        Complex { re, im }
+    }
+    // Retourne le nombre complexe de module 1 et d'argument donné.
+    pub fn from_angle(angle: f64) -> Self {
+        Complex::new(angle.cos(), angle.sin())
+    }
+    pub fn conj(self) -> Self {
+        Complex::new(self.re, -self.im)
+    }
+    pub fn norm_squared(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+    pub fn norm(self) -> f64 {
+        self.norm_squared().sqrt()
+    }
+    pub fn scale(self, factor: f64) -> Self {
+        Complex::new(self.re * factor, self.im * factor)
+    }
+    // Retourne la détermination principale du logarithme.
+    pub fn ln(self) -> Self {
+        Complex::new(self.norm().ln(), self.im.atan2(self.re))
+    }
+}
+
+impl From<DVec2> for Complex {
+    fn from(point: DVec2) -> Self {
+        Complex::new(point.x, point.y)
+    }
+}
+
+impl From<Complex> for DVec2 {
+    fn from(complex: Complex) -> Self {
+        DVec2::new(complex.re, complex.im)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+    fn div(self, other: Complex) -> Complex {
+        // On multiplie le numérateur et le dénominateur par le
+        // conjugué du dénominateur.
+        (self * other.conj()).scale(1f64 / other.norm_squared())
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+    fn neg(self) -> Complex {
+        Complex::new(-self.re, -self.im)
+    }
+}
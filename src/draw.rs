@@ -1,31 +1,37 @@
-use crate::circle::Circle;
-use crate::reflect::Reflect;
+use glam::DVec2;
 use nannou::prelude::*;
 
+use crate::render::{Render, Renderer};
+
+// Convertit un point en double précision en un point de Nannou.
+fn to_point2(point: DVec2) -> Point2 {
+    pt2(point.x as f32, point.y as f32)
+}
+
 // Retourne un point de contrôle à partir des deux points aux
 // extremités de l'arc et du centre du cercle qui compose l'arc dont
 // on veut obtenir le point de contrôle
 fn control_point(
-    point: Point2,
-    reference_point: Point2,
-    center: Point2,
-) -> Point2 {
+    point: DVec2,
+    reference_point: DVec2,
+    center: DVec2,
+) -> DVec2 {
     // On calcule la mesure de l'angle AOB avec, A et B étant les
     // extremités et O le centre du cercle dont est issu notre arc.
     let angle = (point - center)
         .angle_between(reference_point - center)
         .abs();
-    let l = 4f32 * (angle / 4f32).tan() / 3f32;
+    let l = 4f64 * (angle / 4f64).tan() / 3f64;
     // On calcule le vecteur directeur de notre tangente.
     let director_tan =
-        Vec2::new(point.y - center.y, center.x - point.x);
+        DVec2::new(point.y - center.y, center.x - point.x);
     // On calcule les deux points sur la tangente au cercle passant
     // par notre extremité, situés à une distance l de celle-ci.
-    let potential_control_point1 = Vec2::new(
+    let potential_control_point1 = DVec2::new(
         point.x + director_tan.x * l,
         point.y + director_tan.y * l,
     );
-    let potential_control_point2 = Vec2::new(
+    let potential_control_point2 = DVec2::new(
         point.x - director_tan.x * l,
         point.y - director_tan.y * l,
     );
@@ -41,57 +47,72 @@ fn control_point(
     }
 }
 
-#[derive(Debug)]
-pub struct Arc {
-    start: Point2,
-    end: Point2,
-    circle: Circle,
+// Moteur de rendu dessinant sur une instance draw de Nannou.
+pub struct NannouRenderer<'a> {
+    draw: &'a Draw,
 }
 
-impl Arc {
-    pub fn new(
-        start: Point2,
-        end: Point2,
-        circle: Circle,
-    ) -> Option<Self> {
-        //On ne retourne rien si les deux points aux extremités sont
-        // égaux, car l'arc n'existerait simplement pas.
-        if start == end {
-            return None;
-        }
-        Some(Arc { start, end, circle })
+impl<'a> NannouRenderer<'a> {
+    pub fn new(draw: &'a Draw) -> Self {
+        NannouRenderer { draw }
     }
 }
 
-impl Reflect for Arc {
-    fn reflect(&self, point: Point2) -> Point2 {
-        // Retourne simplement la réflexion du point du cercle dont
-        // l'arc est issu.
-        self.circle.reflect(point)
+impl Renderer for NannouRenderer<'_> {
+    fn line(&mut self, start: DVec2, end: DVec2) {
+        self.draw
+            .line()
+            .start(to_point2(start))
+            .end(to_point2(end))
+            .color(BLACK)
+            .weight(0.005);
     }
-    fn draw(&self, draw: &Draw) {
+    fn arc(&mut self, start: DVec2, end: DVec2, center: DVec2) {
         // On initialise un constructeur pour notre courbe de Bézier,
         // on calcule les points de contrôle de cette dernière, puis
         // on la construit.
         let mut builder =
             nannou::geom::path::Builder::new().with_svg();
-        builder.move_to(self.start.to_array().into());
+        builder.move_to(to_point2(start).to_array().into());
         builder.cubic_bezier_to(
-            control_point(self.start, self.end, self.circle.center())
+            to_point2(control_point(start, end, center))
                 .to_array()
                 .into(),
-            control_point(self.end, self.start, self.circle.center())
+            to_point2(control_point(end, start, center))
                 .to_array()
                 .into(),
-            self.end.to_array().into(),
+            to_point2(end).to_array().into(),
         );
         let path = builder.build();
         // On dessine notre courbe
-        draw.path()
+        self.draw
+            .path()
             .stroke()
             .tolerance(0.001)
             .weight(0.005)
             .color(BLACK)
             .events(path.iter());
     }
+    fn circle(&mut self, center: DVec2, radius: f64) {
+        self.draw
+            .ellipse()
+            .resolution(64f32)
+            .no_fill()
+            .stroke(BLACK)
+            .stroke_weight(0.005)
+            .xy(to_point2(center))
+            .radius(radius as f32);
+    }
+}
+
+// Permet de dessiner directement sur une instance draw de Nannou toute
+// structure pouvant être dessinée.
+pub trait NannouDraw {
+    fn draw(&self, draw: &Draw);
+}
+
+impl<T: Render + ?Sized> NannouDraw for T {
+    fn draw(&self, draw: &Draw) {
+        self.render(&mut NannouRenderer::new(draw));
+    }
 }
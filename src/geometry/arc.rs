@@ -0,0 +1,78 @@
+use super::Circle;
+use crate::mobius::Mobius;
+use crate::render::*;
+use glam::DVec2;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Arc {
+    start: DVec2,
+    end: DVec2,
+    circle: Circle,
+}
+
+impl Arc {
+    pub fn new(
+        start: DVec2,
+        end: DVec2,
+        circle: Circle,
+    ) -> Option<Self> {
+        //On ne retourne rien si les deux points aux extremités sont
+        // égaux, car l'arc n'existerait simplement pas.
+        if start == end {
+            return None;
+        }
+        Some(Arc { start, end, circle })
+    }
+    pub fn start(&self) -> DVec2 {
+        self.start
+    }
+    pub fn end(&self) -> DVec2 {
+        self.end
+    }
+    pub fn circle(&self) -> &Circle {
+        &self.circle
+    }
+    pub fn reflect(&self, point: DVec2) -> DVec2 {
+        // Retourne simplement la réflexion du point du cercle dont
+        // l'arc est issu.
+        self.circle.reflect(point)
+    }
+    // Retourne la réflexion sous la forme d'une isométrie.
+    pub fn reflection(&self) -> Mobius {
+        self.circle.reflection()
+    }
+    pub fn ideal_endpoints(&self) -> (DVec2, DVec2) {
+        // Les extremités idéales sont les intersections du cercle
+        // dont est issu l'arc avec le cercle unité. On retourne en
+        // premier la plus proche du début de l'arc.
+        let unit_circle = Circle::new(DVec2::ZERO, 1f64).unwrap();
+        let (a, b) = self
+            .circle
+            .intersect(&unit_circle)
+            .unwrap_or((self.start, self.end));
+        if a.distance_squared(self.start)
+            <= b.distance_squared(self.start)
+        {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+    // Retourne une valeur négative pour les points situés à
+    // l'intérieur du cercle dont est issu l'arc, positive pour ceux
+    // situés à l'extérieur.
+    pub fn side(&self, point: DVec2) -> f64 {
+        point.distance_squared(self.circle.center())
+            - self.circle.radius().powi(2)
+    }
+}
+
+impl Render for Arc {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        renderer.arc(self.start, self.end, self.circle.center());
+    }
+}
@@ -0,0 +1,88 @@
+use core::fmt::Debug;
+use glam::DVec2;
+
+use crate::mobius::Mobius;
+use crate::render::*;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Circle {
+    center: DVec2,
+    radius: f64,
+}
+
+impl Circle {
+    pub fn new(center: DVec2, radius: f64) -> Option<Self> {
+        // Si le rayon est négatif, on ne retourne pas de cercle.
+        if radius < 0f64 {
+            return None;
+        }
+        Some(Circle { center, radius })
+    }
+    pub fn center(&self) -> DVec2 {
+        self.center
+    }
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+    // Retourne les points d'intersection entre deux cercles, s'ils
+    // existent.
+    pub fn intersect(
+        &self,
+        circle: &Circle,
+    ) -> Option<(DVec2, DVec2)> {
+        let distance = self.center.distance(circle.center);
+        if distance == 0f64 {
+            return None;
+        }
+        // On calcule la distance entre notre centre et la droite
+        // passant par les deux points d'intersection, puis la
+        // distance entre cette droite et chacun des points.
+        let along = (distance.powi(2) + self.radius.powi(2)
+            - circle.radius.powi(2))
+            / (2f64 * distance);
+        let across_squared = self.radius.powi(2) - along.powi(2);
+        if across_squared < 0f64 {
+            return None;
+        }
+        let direction = (circle.center - self.center) / distance;
+        let base = self.center + direction * along;
+        let offset = direction.perp() * across_squared.sqrt();
+        Some((base - offset, base + offset))
+    }
+    // Retourne l'inverse du point en paramètre
+    pub fn reflect(&self, point: DVec2) -> DVec2 {
+        // La distance entre le centre et un point multipliée par la
+        // distance entre le centre et l'inverse du point est égale au
+        // carré du rayon. (|OI|*|OA|=r*r) Le centre, le
+        // point, et son inverse son colinéaires
+        let distance_of_inverted_point =
+            self.radius.powi(2) / point.distance(self.center);
+        if distance_of_inverted_point == self.radius {
+            return point;
+        }
+        // On crée un vecteur colinéaire avec le centre, le point et
+        // son inverse, puis on le normalise et finalement retourne la
+        // distance entre le centre et l'inverse multipliée par le
+        // vecteur normal, donc l'inverse.
+        let normalized_vec = DVec2::new(
+            point.x - self.center.x,
+            point.y - self.center.y,
+        )
+        .normalize_or_zero();
+        self.center + normalized_vec * distance_of_inverted_point
+    }
+    // Retourne l'inversion sous la forme d'une isométrie.
+    pub fn reflection(&self) -> Mobius {
+        Mobius::circle_inversion(self.center, self.radius)
+    }
+}
+
+impl Render for Circle {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        renderer.circle(self.center, self.radius);
+    }
+}
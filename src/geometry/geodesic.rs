@@ -0,0 +1,116 @@
+use glam::DVec2;
+
+use super::geodesic_passing_by_two_points;
+use super::hyperbolic_distance;
+use super::Arc;
+use super::Line;
+use crate::mobius::Mobius;
+use crate::model::klein;
+use crate::render::*;
+
+// Géodésique du disque de Poincaré. Elle est soit portée par un
+// diamètre du disque, soit par un arc de cercle orthogonal au bord
+// du disque.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Geodesic {
+    Diameter(Line),
+    Arc(Arc),
+}
+
+impl Geodesic {
+    // Retourne les extremités du segment de géodésique.
+    pub fn start(&self) -> DVec2 {
+        match self {
+            Geodesic::Diameter(line) => line.start(),
+            Geodesic::Arc(arc) => arc.start(),
+        }
+    }
+    pub fn end(&self) -> DVec2 {
+        match self {
+            Geodesic::Diameter(line) => line.end(),
+            Geodesic::Arc(arc) => arc.end(),
+        }
+    }
+    // Retourne la symétrie d'un point par rapport à la géodésique.
+    pub fn reflect(&self, point: DVec2) -> DVec2 {
+        match self {
+            Geodesic::Diameter(line) => line.reflect(point),
+            Geodesic::Arc(arc) => arc.reflect(point),
+        }
+    }
+    // Retourne la réflexion sous la forme d'une isométrie, afin de
+    // pouvoir la composer.
+    pub fn reflection(&self) -> Mobius {
+        match self {
+            Geodesic::Diameter(line) => line.reflection(),
+            Geodesic::Arc(arc) => arc.reflection(),
+        }
+    }
+    // Retourne l'image de la géodésique par une isométrie.
+    pub fn transform(&self, mobius: &Mobius) -> Option<Geodesic> {
+        // L'image d'un segment de géodésique est le segment de
+        // géodésique reliant les images de ses extremités.
+        geodesic_passing_by_two_points(
+            mobius.apply(self.start()),
+            mobius.apply(self.end()),
+        )
+    }
+    // Retourne les deux points du bord du disque vers lesquels tend
+    // la géodésique.
+    pub fn ideal_endpoints(&self) -> (DVec2, DVec2) {
+        match self {
+            Geodesic::Diameter(line) => line.ideal_endpoints(),
+            Geodesic::Arc(arc) => arc.ideal_endpoints(),
+        }
+    }
+    // Retourne une valeur dont le signe indique de quel côté de la
+    // géodésique se trouve un point. Elle est nulle sur la géodésique.
+    pub fn side(&self, point: DVec2) -> f64 {
+        match self {
+            Geodesic::Diameter(line) => line.side(point),
+            Geodesic::Arc(arc) => arc.side(point),
+        }
+    }
+    // Vérifie si deux points se trouvent du même côté de la
+    // géodésique.
+    pub fn same_side(&self, a: DVec2, b: DVec2) -> bool {
+        self.side(a).signum() == self.side(b).signum()
+    }
+    // Retourne la distance hyperbolique entre un point et la
+    // géodésique.
+    pub fn distance(&self, point: DVec2) -> f64 {
+        // Le point et son symétrique sont situés à la même distance
+        // de la géodésique, de part et d'autre de celle-ci.
+        hyperbolic_distance(point, self.reflect(point)) / 2f64
+    }
+    // Retourne le point d'intersection entre deux géodésiques, s'il
+    // existe.
+    pub fn intersect(&self, other: &Geodesic) -> Option<DVec2> {
+        // Dans le modèle de Klein, les géodésiques sont les cordes
+        // reliant leurs extremités idéales, qui sont les mêmes que
+        // dans le modèle de Poincaré. On calcule donc l'intersection
+        // des deux cordes, puis on revient dans le modèle de
+        // Poincaré.
+        let (a, b) = self.ideal_endpoints();
+        let (c, d) = other.ideal_endpoints();
+        let intersection =
+            Line::new(a, b)?.intersect(Line::new(c, d)?)?;
+        if intersection.length_squared() >= 1f64 {
+            return None;
+        }
+        Some(klein::to_poincare(intersection))
+    }
+}
+
+impl Render for Geodesic {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        match self {
+            Geodesic::Diameter(line) => line.render(renderer),
+            Geodesic::Arc(arc) => arc.render(renderer),
+        }
+    }
+}
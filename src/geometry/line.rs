@@ -1,17 +1,22 @@
 use core::fmt::Debug;
-use nannou::prelude::*;
+use glam::DVec2;
 
-use crate::reflect::*;
+use crate::mobius::Mobius;
+use crate::render::*;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct Line {
-    start: Point2,
-    end: Point2,
-    direction: Vec2,
+    start: DVec2,
+    end: DVec2,
+    direction: DVec2,
 }
 
 impl Line {
-    pub fn new(start: Point2, end: Point2) -> Option<Self> {
+    pub fn new(start: DVec2, end: DVec2) -> Option<Self> {
         // Si les deux points sur la droite sont égaux, on ne peut pas
         // calculer le vecteur directeur de la droite. Alors, on ne
         // retourne pas de droite.
@@ -26,16 +31,22 @@ impl Line {
             direction,
         })
     }
+    pub fn start(&self) -> DVec2 {
+        self.start
+    }
+    pub fn end(&self) -> DVec2 {
+        self.end
+    }
     // Retourne la droite orthogonale passant par un point donné
     pub fn orthogonal_line_passing_by_point(
         &self,
-        point: Point2,
+        point: DVec2,
     ) -> Self {
         // On calcule le vecteur orthogonal à notre droite pour
         // déterminer un 2ème point de celle-ci, en additionant notre
         // point donné avec notre vecteur.
         let orthogonal_direction =
-            Vec2::new(-self.direction.y, self.direction.x);
+            DVec2::new(-self.direction.y, self.direction.x);
         Line {
             start: point,
             end: point + orthogonal_direction,
@@ -43,7 +54,7 @@ impl Line {
         }
     }
     // Retourne le point d'intersection entre deux droites
-    pub fn intersect(&self, line: Self) -> Option<Point2> {
+    pub fn intersect(&self, line: Self) -> Option<DVec2> {
         // Issu de l'équation cartésienne d'une droite:
         // x = x0
         //          + k * v, on cherche le facteur k avec x,y étant
@@ -60,36 +71,55 @@ impl Line {
         if !factor.is_finite() {
             return None;
         }
-        Some(Point2::new(
+        Some(DVec2::new(
             self.start.x + factor * self.direction.x,
             self.start.y + factor * self.direction.y,
         ))
     }
     // Retourne la projection orthogonale d'un point sur notre droite.
-    pub fn projection(&self, point: Point2) -> Point2 {
+    pub fn projection(&self, point: DVec2) -> DVec2 {
         let orthogonal = self.orthogonal_line_passing_by_point(point);
         self.intersect(orthogonal).unwrap()
     }
-}
-
-impl Reflect for Line {
     // Retourne la symmétrie d'un point par rapport à notre droite.
-    fn reflect(&self, point: Point2) -> Point2 {
+    pub fn reflect(&self, point: DVec2) -> DVec2 {
         // On calcule la projection orthogonale de notre droite. Le
         // point retourné est à mi-distance entre le point donné et
         // celui qu'on recherche.
         let projection = self.projection(point);
         let direction = projection - point;
-        Point2::new(
-            point.x + direction.x * 2f32,
-            point.y + direction.y * 2f32,
+        DVec2::new(
+            point.x + direction.x * 2f64,
+            point.y + direction.y * 2f64,
+        )
+    }
+    // Retourne la symmétrie sous la forme d'une isométrie.
+    pub fn reflection(&self) -> Mobius {
+        Mobius::line_reflection(self.start, self.direction)
+    }
+    // Retourne les intersections de la droite avec le cercle unité.
+    pub fn ideal_endpoints(&self) -> (DVec2, DVec2) {
+        // On cherche les facteurs k tels que le point start + k * v
+        // soit sur le cercle unité, v étant le vecteur directeur. Ce
+        // sont les solutions d'une équation du second degré.
+        let a = self.direction.length_squared();
+        let b = 2f64 * self.start.dot(self.direction);
+        let c = self.start.length_squared() - 1f64;
+        let root = (b.powi(2) - 4f64 * a * c).max(0f64).sqrt();
+        (
+            self.start + self.direction * ((-b - root) / (2f64 * a)),
+            self.start + self.direction * ((-b + root) / (2f64 * a)),
         )
     }
-    fn draw(&self, draw: &Draw) {
-        draw.line()
-            .start(self.start)
-            .end(self.end)
-            .color(BLACK)
-            .weight(0.005);
+    // Retourne une valeur dont le signe indique de quel côté de la
+    // droite se trouve un point.
+    pub fn side(&self, point: DVec2) -> f64 {
+        self.direction.perp_dot(point - self.start)
+    }
+}
+
+impl Render for Line {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        renderer.line(self.start, self.end);
     }
 }
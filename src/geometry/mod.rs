@@ -0,0 +1,187 @@
+// Objets géométriques du disque de Poincaré et fonctions
+// hyperboliques usuelles. Toutes les coordonnées sont celles du
+// disque de Poincaré.
+mod arc;
+mod circle;
+mod geodesic;
+mod line;
+
+pub use arc::Arc;
+pub use circle::Circle;
+pub use geodesic::Geodesic;
+pub use line::Line;
+
+use glam::DVec2;
+
+use crate::mobius::Mobius;
+
+// Retourne la distance hyperbolique entre deux points.
+pub fn hyperbolic_distance(u: DVec2, v: DVec2) -> f64 {
+    let divisor = ((1f64 - u.length_squared())
+        * (1f64 - v.length_squared()))
+    .max(f64::MIN_POSITIVE);
+    (1f64 + 2f64 * u.distance_squared(v) / divisor).acosh()
+}
+
+// Retourne le milieu hyperbolique de deux points.
+pub fn hyperbolic_midpoint(u: DVec2, v: DVec2) -> DVec2 {
+    // On ramène u à l'origine. Le milieu se trouve alors sur le
+    // rayon passant par l'image de v, à une distance hyperbolique
+    // d/2 de l'origine, soit une distance euclidienne tanh(d/4).
+    let to_origin = Mobius::translation(u, DVec2::ZERO);
+    let image = to_origin.apply(v);
+    if image.length() == 0f64 {
+        return u;
+    }
+    let distance = hyperbolic_distance(u, v);
+    let midpoint = image.normalize() * (distance / 4f64).tanh();
+    to_origin.inverse().apply(midpoint)
+}
+
+// Retourne l'angle, compris entre 0 et π, formé en un sommet par les
+// géodésiques le reliant à deux autres points.
+pub fn angle_at_vertex(a: DVec2, vertex: DVec2, b: DVec2) -> f64 {
+    // Le modèle étant conforme, on peut ramener le sommet à
+    // l'origine : les géodésiques deviennent des diamètres et l'angle
+    // euclidien est l'angle hyperbolique.
+    let to_origin = Mobius::translation(vertex, DVec2::ZERO);
+    to_origin.apply(a).angle_between(to_origin.apply(b)).abs()
+}
+
+// Retourne la géodésique complète passant par un point et orthogonale
+// à une autre géodésique. Ses extrémités sont ses points à l'infini.
+pub fn perpendicular_geodesic_passing_by_point(
+    geodesic: &Geodesic,
+    point: DVec2,
+) -> Option<Geodesic> {
+    // On ramène le point à l'origine. La perpendiculaire est alors un
+    // diamètre : orthogonal à la géodésique si celle-ci passe par
+    // l'origine, dirigé vers le milieu de ses points à l'infini sinon.
+    let to_origin = Mobius::translation(point, DVec2::ZERO);
+    let (a, b) = geodesic.transform(&to_origin)?.ideal_endpoints();
+    let direction = if (a + b).length() < 1e-12 {
+        a.perp()
+    } else {
+        (a + b).normalize()
+    };
+    let from_origin = to_origin.inverse();
+    geodesic_passing_by_two_points(
+        from_origin.apply(-direction),
+        from_origin.apply(direction),
+    )
+}
+
+// Rayon au-delà duquel une géodésique est assimilée à une droite.
+pub(crate) const MAX_ARC_RADIUS: f64 = 1e6;
+
+pub fn geodesic_passing_by_two_points(
+    u: DVec2,
+    v: DVec2,
+) -> Option<Geodesic> {
+    let divisor = u.x * v.y - u.y * v.x;
+    // Si le diviseur vaut zéro l'équation du cercle tend vers
+    // l'infini, donc on construit une droite si les points ne sont
+    // pas égaux.
+    if divisor == 0f64 {
+        return Line::new(u, v).map(Geodesic::Diameter);
+    }
+    // Issu de l'équation du cercle : x*x + ax + y*y + by + 1 = 0
+    let factor_of_x = (u.y * (v.x.powi(2) + v.y.powi(2))
+        - v.y * (u.x.powi(2) + u.y.powi(2))
+        + u.y
+        - v.y)
+        / divisor;
+
+    let factor_of_y = (v.x * (u.x.powi(2) + u.y.powi(2))
+        - u.x * (v.x.powi(2) + v.y.powi(2))
+        + v.x
+        - u.x)
+        / divisor;
+    let center = DVec2::new(-factor_of_x / 2f64, -factor_of_y / 2f64);
+    let radius = center.distance(u);
+    // Si le cercle est énorme, les deux points sont presque alignés
+    // avec l'origine. On peut alors faire une approximation et partir
+    // du principe que c'est une ligne pour des inversions plus
+    // précises, l'écart entre les deux étant inférieur à l'inverse du
+    // rayon.
+    if !radius.is_finite() || radius > MAX_ARC_RADIUS {
+        return Line::new(u, v).map(Geodesic::Diameter);
+    }
+    if let Some(circle) = Circle::new(center, radius) {
+        if let Some(arc) = Arc::new(u, v, circle) {
+            return Some(Geodesic::Arc(arc));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // La perpendiculaire passe par le point et coupe la géodésique à
+    // angle droit, que celle-ci soit un arc ou un diamètre.
+    #[test]
+    fn perpendicular_is_orthogonal() {
+        for (u, v, point) in [
+            (
+                DVec2::new(0.1, 0.4),
+                DVec2::new(-0.5, 0.2),
+                DVec2::new(0.3, -0.2),
+            ),
+            (
+                DVec2::new(-0.3, -0.3),
+                DVec2::new(0.6, 0.6),
+                DVec2::new(-0.4, 0.5),
+            ),
+            (
+                DVec2::new(-0.3, -0.3),
+                DVec2::new(0.6, 0.6),
+                DVec2::ZERO,
+            ),
+        ] {
+            let geodesic =
+                geodesic_passing_by_two_points(u, v).unwrap();
+            let perpendicular =
+                perpendicular_geodesic_passing_by_point(
+                    &geodesic, point,
+                )
+                .unwrap();
+            assert!(perpendicular.distance(point) < 1e-9);
+            // Les deux géodésiques complètes se coupent au pied de la
+            // perpendiculaire, là où l'angle est mesuré.
+            let (a, b) = geodesic.ideal_endpoints();
+            let complete =
+                geodesic_passing_by_two_points(a, b).unwrap();
+            let foot = complete.intersect(&perpendicular).unwrap();
+            let (c, _) = perpendicular.ideal_endpoints();
+            let angle = angle_at_vertex(a, foot, c);
+            assert!(
+                (angle - std::f64::consts::FRAC_PI_2).abs() < 1e-6
+            );
+        }
+    }
+
+    // Le milieu est équidistant des deux points, à la moitié de leur
+    // distance.
+    #[test]
+    fn midpoint_is_equidistant() {
+        let points = [
+            DVec2::ZERO,
+            DVec2::new(0.3, -0.2),
+            DVec2::new(-0.7, 0.5),
+            DVec2::new(0.05, 0.9),
+        ];
+        for u in points {
+            for v in points {
+                let midpoint = hyperbolic_midpoint(u, v);
+                let distance = hyperbolic_distance(u, v);
+                for endpoint in [u, v] {
+                    let half =
+                        hyperbolic_distance(midpoint, endpoint);
+                    assert!((2f64 * half - distance).abs() < 1e-6);
+                }
+            }
+        }
+    }
+}
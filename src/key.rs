@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use glam::DVec2;
+
+use crate::geometry::hyperbolic_distance;
+
+// Taille (en distance hyperbolique) d'une cellule de notre grille.
+const CELL_SIZE: f64 = 0.25;
+// Distance hyperbolique en dessous de laquelle deux points sont
+// considérés comme égaux. Elle doit rester inférieure à CELL_SIZE.
+const TOLERANCE: f64 = 0.1;
+
+// Clé canonique d'un point du disque de Poincaré. On découpe le
+// disque en anneaux de même épaisseur hyperbolique, puis chaque
+// anneau en secteurs de même longueur hyperbolique. Ainsi, la taille
+// des cellules ne dépend pas de la proximité avec le bord du disque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Key {
+    ring: i64,
+    sector: i64,
+}
+
+// Retourne la distance hyperbolique entre l'origine et un point.
+fn distance_from_origin(point: DVec2) -> f64 {
+    // On borne la norme afin que les erreurs d'arrondi ne nous
+    // fassent pas sortir du disque.
+    let norm = point.length().min(1f64 - f64::EPSILON);
+    ((1f64 + norm) / (1f64 - norm)).ln()
+}
+
+// Retourne le nombre de secteurs composant un anneau.
+fn sectors_in_ring(ring: i64) -> i64 {
+    let radius = ring as f64 * CELL_SIZE;
+    ((2f64 * PI * radius.sinh() / CELL_SIZE).ceil() as i64).max(1)
+}
+
+// Retourne le secteur d'un anneau dans lequel se trouve un angle.
+fn sector(ring: i64, angle: f64) -> i64 {
+    let sectors = sectors_in_ring(ring);
+    (((angle + PI) / (2f64 * PI) * sectors as f64).floor() as i64)
+        .rem_euclid(sectors)
+}
+
+// Retourne les clés de la cellule du point et de ses voisines, dans
+// lesquelles peut se trouver un point qui lui est égal.
+fn neighbouring_keys(point: DVec2) -> Vec<Key> {
+    let ring =
+        (distance_from_origin(point) / CELL_SIZE).floor() as i64;
+    let angle = point.y.atan2(point.x);
+    let mut keys = vec![];
+    for ring in (ring - 1).max(0)..=ring + 1 {
+        let sectors = sectors_in_ring(ring);
+        let sector = sector(ring, angle);
+        for offset in -1..=1 {
+            let key = Key {
+                ring,
+                sector: (sector + offset).rem_euclid(sectors),
+            };
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+    keys
+}
+
+// Table associant une valeur à des points du disque de Poincaré. Deux
+// points séparés par une distance hyperbolique inférieure à la
+// tolérance désignent la même entrée, ce qui permet de retrouver un
+// point malgré les erreurs d'arrondi.
+#[derive(Debug)]
+pub struct PointMap<T> {
+    cells: HashMap<Key, Vec<(DVec2, T)>>,
+    len: usize,
+}
+
+impl<T> Default for PointMap<T> {
+    fn default() -> Self {
+        PointMap {
+            cells: HashMap::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<T> PointMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    // Retourne la clé et la position dans la cellule de l'entrée
+    // correspondant au point, si elle existe.
+    fn find(&self, point: DVec2) -> Option<(Key, usize)> {
+        for key in neighbouring_keys(point) {
+            if let Some(cell) = self.cells.get(&key) {
+                if let Some(index) =
+                    cell.iter().position(|(other, _)| {
+                        hyperbolic_distance(point, *other) < TOLERANCE
+                    })
+                {
+                    return Some((key, index));
+                }
+            }
+        }
+        None
+    }
+    pub fn get(&self, point: DVec2) -> Option<&T> {
+        let (key, index) = self.find(point)?;
+        Some(&self.cells[&key][index].1)
+    }
+    // Ajoute une entrée sans vérifier qu'elle n'existe pas déjà.
+    pub fn insert(&mut self, point: DVec2, value: T) {
+        let ring =
+            (distance_from_origin(point) / CELL_SIZE).floor() as i64;
+        let key = Key {
+            ring,
+            sector: sector(ring, point.y.atan2(point.x)),
+        };
+        self.cells.entry(key).or_default().push((point, value));
+        self.len += 1;
+    }
+}
+
+// Écart angulaire en dessous duquel deux points du bord du disque
+// sont considérés comme égaux.
+const BOUNDARY_TOLERANCE: f64 = 1e-9;
+// Nombre de secteurs découpant le bord du disque. Chaque secteur est
+// plus large que la tolérance.
+const BOUNDARY_SECTORS: i64 =
+    (PI / (5f64 * BOUNDARY_TOLERANCE)) as i64;
+
+// Retourne le secteur du bord dans lequel se trouve un angle.
+fn boundary_sector(angle: f64) -> i64 {
+    ((angle.rem_euclid(2f64 * PI) / (2f64 * PI)
+        * BOUNDARY_SECTORS as f64)
+        .floor() as i64)
+        .rem_euclid(BOUNDARY_SECTORS)
+}
+
+// Retourne l'écart entre deux angles, compris entre 0 et π.
+fn angular_distance(a: f64, b: f64) -> f64 {
+    let difference = (a - b).rem_euclid(2f64 * PI);
+    difference.min(2f64 * PI - difference)
+}
+
+// Table associant une valeur à des points du bord du disque (points
+// à l'infini). La distance hyperbolique n'y étant pas définie, deux
+// points sont identifiés par leur angle.
+#[derive(Debug)]
+pub struct BoundaryMap<T> {
+    cells: HashMap<i64, Vec<(f64, T)>>,
+    len: usize,
+}
+
+impl<T> Default for BoundaryMap<T> {
+    fn default() -> Self {
+        BoundaryMap {
+            cells: HashMap::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<T> BoundaryMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn get(&self, point: DVec2) -> Option<&T> {
+        let angle = point.y.atan2(point.x);
+        let sector = boundary_sector(angle);
+        for offset in -1..=1 {
+            let key = (sector + offset).rem_euclid(BOUNDARY_SECTORS);
+            if let Some(cell) = self.cells.get(&key) {
+                if let Some((_, value)) =
+                    cell.iter().find(|(other, _)| {
+                        angular_distance(angle, *other)
+                            < BOUNDARY_TOLERANCE
+                    })
+                {
+                    return Some(value);
+                }
+            }
+        }
+        None
+    }
+    // Ajoute une entrée sans vérifier qu'elle n'existe pas déjà.
+    pub fn insert(&mut self, point: DVec2, value: T) {
+        let angle = point.y.atan2(point.x);
+        self.cells
+            .entry(boundary_sector(angle))
+            .or_default()
+            .push((angle, value));
+        self.len += 1;
+    }
+}
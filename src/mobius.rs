@@ -0,0 +1,310 @@
+use glam::DVec2;
+
+use crate::complex::Complex;
+use crate::geometry::Geodesic;
+
+// Isométrie du disque de Poincaré. Elle est représentée par une
+// matrice complexe 2x2 (a b; c d) qui associe à un point z le point
+// (aw + b) / (cw + d), avec w = z si l'isométrie conserve
+// l'orientation, ou w le conjugué de z si elle la renverse (c'est le
+// cas des réflexions).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mobius {
+    a: Complex,
+    b: Complex,
+    c: Complex,
+    d: Complex,
+    reversing: bool,
+}
+
+impl Mobius {
+    // Construit une isométrie à partir de sa matrice, en la
+    // normalisant afin que son déterminant soit de module 1. Cela
+    // évite que les coefficients ne grandissent au fil des
+    // compositions.
+    fn new(
+        a: Complex,
+        b: Complex,
+        c: Complex,
+        d: Complex,
+        reversing: bool,
+    ) -> Self {
+        let factor = 1f64 / (a * d - b * c).norm().sqrt();
+        Mobius {
+            a: a.scale(factor),
+            b: b.scale(factor),
+            c: c.scale(factor),
+            d: d.scale(factor),
+            reversing,
+        }
+    }
+    pub fn identity() -> Self {
+        Mobius::new(
+            Complex::ONE,
+            Complex::ZERO,
+            Complex::ZERO,
+            Complex::ONE,
+            false,
+        )
+    }
+    // Retourne la rotation d'angle donné autour de l'origine.
+    pub fn rotation(angle: f64) -> Self {
+        Mobius::new(
+            Complex::from_angle(angle),
+            Complex::ZERO,
+            Complex::ZERO,
+            Complex::ONE,
+            false,
+        )
+    }
+    // Retourne la translation le long du diamètre passant par un
+    // point, qui envoie l'origine sur ce point.
+    fn translation_from_origin(point: Complex) -> Self {
+        Mobius::new(
+            Complex::ONE,
+            point,
+            point.conj(),
+            Complex::ONE,
+            false,
+        )
+    }
+    // Retourne la translation le long de la géodésique passant par
+    // deux points, qui envoie le premier point sur le second.
+    pub fn translation(from: DVec2, to: DVec2) -> Self {
+        // On ramène le point de départ à l'origine, on effectue la
+        // translation le long du diamètre passant par l'image du
+        // point d'arrivée, puis on revient.
+        let to_origin =
+            Mobius::translation_from_origin(from.into()).inverse();
+        let to = to_origin.apply_complex(to.into());
+        to_origin
+            .inverse()
+            .compose(&Mobius::translation_from_origin(to))
+            .compose(&to_origin)
+    }
+    // Retourne la réflexion par rapport à une géodésique.
+    pub fn reflection(geodesic: &Geodesic) -> Self {
+        geodesic.reflection()
+    }
+    // Retourne la réflexion par rapport à la droite passant par un
+    // point et de vecteur directeur donné.
+    pub fn line_reflection(point: DVec2, direction: DVec2) -> Self {
+        // Avec u le vecteur directeur normalisé, la réflexion associe
+        // à z le point p + u²(w - p'), w et p' étant les conjugués de z
+        // et p.
+        let point: Complex = point.into();
+        let direction: Complex = direction.into();
+        let direction = direction.scale(1f64 / direction.norm());
+        let squared = direction * direction;
+        Mobius::new(
+            squared,
+            point - squared * point.conj(),
+            Complex::ZERO,
+            Complex::ONE,
+            true,
+        )
+    }
+    // Retourne l'inversion par rapport au cercle de centre et de
+    // rayon donnés.
+    pub fn circle_inversion(center: DVec2, radius: f64) -> Self {
+        // L'inversion associe à z le point c + r² / (w - c'), w et c'
+        // étant les conjugués de z et c. On obtient alors
+        // (cw + r² - |c|²) / (w - c').
+        let center: Complex = center.into();
+        Mobius::new(
+            center,
+            Complex::new(
+                radius * radius - center.norm_squared(),
+                0f64,
+            ),
+            Complex::ONE,
+            -center.conj(),
+            true,
+        )
+    }
+    // Retourne l'isométrie qui applique d'abord other, puis self.
+    pub fn compose(&self, other: &Mobius) -> Self {
+        // Si self renverse l'orientation, il s'applique au conjugué
+        // de l'image de other, ce qui revient à conjuguer la matrice
+        // de other.
+        let (a, b, c, d) = if self.reversing {
+            (
+                other.a.conj(),
+                other.b.conj(),
+                other.c.conj(),
+                other.d.conj(),
+            )
+        } else {
+            (other.a, other.b, other.c, other.d)
+        };
+        Mobius::new(
+            self.a * a + self.b * c,
+            self.a * b + self.b * d,
+            self.c * a + self.d * c,
+            self.c * b + self.d * d,
+            self.reversing != other.reversing,
+        )
+    }
+    pub fn inverse(&self) -> Self {
+        // On inverse la matrice, puis on la conjugue si l'isométrie
+        // renverse l'orientation.
+        let (a, b, c, d) = (self.d, -self.b, -self.c, self.a);
+        if self.reversing {
+            Mobius::new(a.conj(), b.conj(), c.conj(), d.conj(), true)
+        } else {
+            Mobius::new(a, b, c, d, false)
+        }
+    }
+    pub fn is_reversing(&self) -> bool {
+        self.reversing
+    }
+    pub(crate) fn apply_complex(&self, z: Complex) -> Complex {
+        let w = if self.reversing { z.conj() } else { z };
+        (self.a * w + self.b) / (self.c * w + self.d)
+    }
+    // Retourne l'image d'un point.
+    pub fn apply(&self, point: DVec2) -> DVec2 {
+        self.apply_complex(point.into()).into()
+    }
+    // Retourne l'image d'une géodésique.
+    pub fn apply_to_geodesic(
+        &self,
+        geodesic: &Geodesic,
+    ) -> Option<Geodesic> {
+        geodesic.transform(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{
+        geodesic_passing_by_two_points, hyperbolic_distance,
+    };
+
+    fn points() -> [DVec2; 4] {
+        [
+            DVec2::ZERO,
+            DVec2::new(0.3, -0.2),
+            DVec2::new(-0.7, 0.5),
+            DVec2::new(0.05, 0.9),
+        ]
+    }
+
+    fn isometries() -> Vec<Mobius> {
+        let geodesic = geodesic_passing_by_two_points(
+            DVec2::new(0.1, 0.4),
+            DVec2::new(-0.5, 0.2),
+        )
+        .unwrap();
+        vec![
+            Mobius::rotation(1.2),
+            Mobius::translation(
+                DVec2::new(0.2, 0.1),
+                DVec2::new(-0.4, 0.6),
+            ),
+            Mobius::reflection(&geodesic),
+            Mobius::line_reflection(
+                DVec2::ZERO,
+                DVec2::new(1.0, 2.0),
+            ),
+            Mobius::translation(DVec2::new(0.5, 0.0), DVec2::ZERO)
+                .compose(&Mobius::reflection(&geodesic)),
+        ]
+    }
+
+    // Composer une isométrie avec son inverse, dans un sens ou dans
+    // l'autre, donne l'identité, qu'elle renverse ou non
+    // l'orientation.
+    #[test]
+    fn inverse_cancels_composition() {
+        for mobius in isometries() {
+            let inverse = mobius.inverse();
+            assert_eq!(inverse.is_reversing(), mobius.is_reversing());
+            for identity in
+                [mobius.compose(&inverse), inverse.compose(&mobius)]
+            {
+                assert!(!identity.is_reversing());
+                for point in points() {
+                    assert!(
+                        identity.apply(point).distance(point) < 1e-9
+                    );
+                }
+            }
+        }
+    }
+
+    // La composée applique d'abord son argument, et renverse
+    // l'orientation si une seule des deux isométries la renverse.
+    #[test]
+    fn compose_applies_other_first() {
+        for first in isometries() {
+            for second in isometries() {
+                let composed = second.compose(&first);
+                assert_eq!(
+                    composed.is_reversing(),
+                    first.is_reversing() != second.is_reversing()
+                );
+                for point in points() {
+                    let expected = second.apply(first.apply(point));
+                    assert!(
+                        composed.apply(point).distance(expected)
+                            < 1e-9
+                    );
+                }
+            }
+        }
+    }
+
+    // Une translation envoie son point de départ sur son point
+    // d'arrivée.
+    #[test]
+    fn translation_moves_from_to() {
+        for from in points() {
+            for to in points() {
+                let translation = Mobius::translation(from, to);
+                assert!(translation.apply(from).distance(to) < 1e-9);
+            }
+        }
+    }
+
+    // La réflexion par rapport à une géodésique coïncide avec la
+    // symétrie calculée par la géodésique elle-même.
+    #[test]
+    fn reflection_matches_geodesic() {
+        for (u, v) in [
+            (DVec2::new(0.1, 0.4), DVec2::new(-0.5, 0.2)),
+            (DVec2::new(-0.3, -0.3), DVec2::new(0.6, 0.6)),
+        ] {
+            let geodesic =
+                geodesic_passing_by_two_points(u, v).unwrap();
+            let reflection = Mobius::reflection(&geodesic);
+            assert!(reflection.is_reversing());
+            for point in points() {
+                assert!(
+                    reflection
+                        .apply(point)
+                        .distance(geodesic.reflect(point))
+                        < 1e-9
+                );
+            }
+        }
+    }
+
+    // Les isométries conservent la distance hyperbolique.
+    #[test]
+    fn isometries_preserve_distance() {
+        for mobius in isometries() {
+            for u in points() {
+                for v in points() {
+                    let distance = hyperbolic_distance(u, v);
+                    let image = hyperbolic_distance(
+                        mobius.apply(u),
+                        mobius.apply(v),
+                    );
+                    assert!((image - distance).abs() < 1e-9);
+                }
+            }
+        }
+    }
+}
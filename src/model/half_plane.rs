@@ -0,0 +1,218 @@
+use glam::DVec2;
+
+use crate::complex::Complex;
+use crate::geometry::{Arc, Circle, Geodesic, Line, MAX_ARC_RADIUS};
+use crate::model::Model;
+use crate::render::*;
+use crate::tiling::Tiling;
+
+// Distance euclidienne au point 1 du disque en dessous de laquelle un
+// point est envoyé à l'infini, plutôt que de diviser par zéro.
+pub(crate) const INFINITY_TOLERANCE: f64 = 1e-12;
+
+// Retourne l'image d'un point du disque de Poincaré dans le
+// demi-plan de Poincaré, par la transformation de Cayley
+// z -> i(1 + z) / (1 - z). Le point 1 du bord du disque est envoyé à
+// l'infini, représenté par une ordonnée infinie.
+pub fn from_poincare(point: DVec2) -> DVec2 {
+    let z: Complex = point.into();
+    if (Complex::ONE - z).norm() < INFINITY_TOLERANCE {
+        return DVec2::new(0f64, f64::INFINITY);
+    }
+    let i = Complex::new(0f64, 1f64);
+    (i * (Complex::ONE + z) / (Complex::ONE - z)).into()
+}
+
+// Retourne l'image d'un point du demi-plan de Poincaré dans le
+// disque, par la transformation réciproque w -> (w - i) / (w + i).
+pub fn to_poincare(point: DVec2) -> DVec2 {
+    if point.y.is_infinite() {
+        return DVec2::X;
+    }
+    let w: Complex = point.into();
+    let i = Complex::new(0f64, 1f64);
+    ((w - i) / (w + i)).into()
+}
+
+// Géodésique du demi-plan de Poincaré. Elle est soit portée par une
+// demi-droite verticale, soit par un demi-cercle centré sur l'axe
+// réel.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum HalfPlaneGeodesic {
+    Vertical(Line),
+    Semicircle(Arc),
+}
+
+impl HalfPlaneGeodesic {
+    pub fn start(&self) -> DVec2 {
+        match self {
+            HalfPlaneGeodesic::Vertical(line) => line.start(),
+            HalfPlaneGeodesic::Semicircle(arc) => arc.start(),
+        }
+    }
+    pub fn end(&self) -> DVec2 {
+        match self {
+            HalfPlaneGeodesic::Vertical(line) => line.end(),
+            HalfPlaneGeodesic::Semicircle(arc) => arc.end(),
+        }
+    }
+}
+
+impl Render for HalfPlaneGeodesic {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        match self {
+            HalfPlaneGeodesic::Vertical(line) => {
+                line.render(renderer)
+            }
+            HalfPlaneGeodesic::Semicircle(arc) => {
+                arc.render(renderer)
+            }
+        }
+    }
+}
+
+// Retourne la géodésique du demi-plan passant par deux points.
+pub fn geodesic_passing_by_two_points(
+    u: DVec2,
+    v: DVec2,
+) -> Option<HalfPlaneGeodesic> {
+    // Une géodésique passant par le point à l'infini est une
+    // demi-droite verticale, que l'on arrête à la hauteur
+    // MAX_ARC_RADIUS afin de pouvoir la dessiner.
+    match (u.y.is_infinite(), v.y.is_infinite()) {
+        (true, true) => return None,
+        (true, false) => {
+            return Line::new(DVec2::new(v.x, MAX_ARC_RADIUS), v)
+                .map(HalfPlaneGeodesic::Vertical)
+        }
+        (false, true) => {
+            return Line::new(u, DVec2::new(u.x, MAX_ARC_RADIUS))
+                .map(HalfPlaneGeodesic::Vertical)
+        }
+        (false, false) => {}
+    }
+    // Le centre du demi-cercle est le point de l'axe réel
+    // équidistant des deux points. Si les deux points sont sur une
+    // même verticale, il n'existe pas et la géodésique est verticale.
+    let divisor = 2f64 * (u.x - v.x);
+    if divisor == 0f64 {
+        return Line::new(u, v).map(HalfPlaneGeodesic::Vertical);
+    }
+    let center = DVec2::new(
+        (u.length_squared() - v.length_squared()) / divisor,
+        0f64,
+    );
+    let radius = center.distance(u);
+    // Comme dans le disque, un demi-cercle énorme est assimilé à une
+    // droite verticale.
+    if !radius.is_finite() || radius > MAX_ARC_RADIUS {
+        return Line::new(u, v).map(HalfPlaneGeodesic::Vertical);
+    }
+    Arc::new(u, v, Circle::new(center, radius)?)
+        .map(HalfPlaneGeodesic::Semicircle)
+}
+
+// Retourne l'image d'une géodésique du disque dans le demi-plan.
+pub fn geodesic(geodesic: &Geodesic) -> Option<HalfPlaneGeodesic> {
+    geodesic_passing_by_two_points(
+        from_poincare(geodesic.start()),
+        from_poincare(geodesic.end()),
+    )
+}
+
+// Pavage exprimé dans le demi-plan de Poincaré.
+pub type HalfPlane = Model<DVec2, HalfPlaneGeodesic>;
+
+impl HalfPlane {
+    // Convertit un pavage calculé. Si le pavage n'a pas été calculé,
+    // on ne retourne rien.
+    pub fn new(tiling: &Tiling) -> Option<Self> {
+        Model::convert(tiling, from_poincare, geodesic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{
+        geodesic_passing_by_two_points as disk_geodesic,
+        hyperbolic_distance, hyperbolic_midpoint,
+    };
+
+    // Retourne la distance hyperbolique entre deux points du
+    // demi-plan.
+    fn half_plane_distance(u: DVec2, v: DVec2) -> f64 {
+        (1f64 + u.distance_squared(v) / (2f64 * u.y * v.y)).acosh()
+    }
+
+    // Les conversions sont réciproques l'une de l'autre et conservent
+    // la distance hyperbolique. Le point 1 est envoyé à l'infini, et
+    // en revient.
+    #[test]
+    fn conversions_are_isometric() {
+        let points = [
+            DVec2::ZERO,
+            DVec2::new(0.3, -0.2),
+            DVec2::new(-0.7, 0.5),
+            DVec2::new(0.05, 0.9),
+        ];
+        for u in points {
+            let image = from_poincare(u);
+            assert!(image.y > 0f64);
+            assert!(to_poincare(image).distance(u) < 1e-9);
+            for v in points {
+                let distance =
+                    half_plane_distance(image, from_poincare(v));
+                assert!(
+                    (distance - hyperbolic_distance(u, v)).abs()
+                        < 1e-9
+                );
+            }
+        }
+        assert!(from_poincare(DVec2::X).y.is_infinite());
+        assert_eq!(to_poincare(from_poincare(DVec2::X)), DVec2::X);
+    }
+
+    // L'image d'un arc est un demi-cercle centré sur l'axe réel
+    // passant par l'image de ses points, et celle d'un diamètre
+    // passant par 1 est verticale.
+    #[test]
+    fn geodesics_are_semicircles_or_verticals() {
+        let arc = disk_geodesic(
+            DVec2::new(0.1, 0.4),
+            DVec2::new(-0.5, 0.2),
+        )
+        .unwrap();
+        let midpoint = from_poincare(hyperbolic_midpoint(
+            arc.start(),
+            arc.end(),
+        ));
+        match geodesic(&arc).unwrap() {
+            HalfPlaneGeodesic::Semicircle(semicircle) => {
+                let circle = semicircle.circle();
+                assert!(circle.center().y.abs() < 1e-9);
+                assert!(
+                    (circle.center().distance(midpoint)
+                        - circle.radius())
+                    .abs()
+                        < 1e-9
+                );
+            }
+            HalfPlaneGeodesic::Vertical(_) => panic!("arc vertical"),
+        }
+        let diameter =
+            disk_geodesic(DVec2::new(-0.5, 0.0), DVec2::X).unwrap();
+        match geodesic(&diameter).unwrap() {
+            HalfPlaneGeodesic::Vertical(line) => {
+                assert!((line.start().x - line.end().x).abs() < 1e-9);
+            }
+            HalfPlaneGeodesic::Semicircle(_) => {
+                panic!("diamètre non vertical")
+            }
+        }
+    }
+}
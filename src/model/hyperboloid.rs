@@ -0,0 +1,243 @@
+use glam::{DMat3, DVec2, DVec3};
+
+use crate::geometry::Geodesic;
+use crate::mobius::Mobius;
+use crate::model::Model;
+use crate::tiling::{Tiling, IDEAL_NORM};
+
+// Forme bilinéaire de Minkowski, de signature (+, -, -). Les points
+// du modèle de l'hyperboloïde vérifient <x, x> = 1 et x0 > 0.
+pub fn minkowski_product(u: DVec3, v: DVec3) -> f64 {
+    u.x * v.x - u.y * v.y - u.z * v.z
+}
+
+// Retourne la distance hyperbolique entre deux points de
+// l'hyperboloïde.
+pub fn distance(u: DVec3, v: DVec3) -> f64 {
+    // Les erreurs d'arrondi peuvent donner un produit légèrement
+    // inférieur à 1, on le borne donc.
+    minkowski_product(u, v).max(1f64).acosh()
+}
+
+// Vérifie si un point est sur le cône de lumière <x, x> = 0 plutôt
+// que sur l'hyperboloïde, c'est-à-dire s'il représente un point idéal.
+fn is_ideal(point: DVec3) -> bool {
+    minkowski_product(point, point) < 0.5
+}
+
+// Retourne l'image d'un point du disque de Poincaré sur
+// l'hyperboloïde. Un point idéal étant à l'infini sur l'hyperboloïde,
+// on le représente par la direction du cône de lumière vers laquelle
+// il tend, normalisée par x0 = 1.
+pub fn from_poincare(point: DVec2) -> DVec3 {
+    let norm_squared = point.length_squared();
+    if norm_squared >= IDEAL_NORM * IDEAL_NORM {
+        let direction = point / norm_squared.sqrt();
+        return DVec3::new(1f64, direction.x, direction.y);
+    }
+    DVec3::new(1f64 + norm_squared, 2f64 * point.x, 2f64 * point.y)
+        / (1f64 - norm_squared)
+}
+
+// Retourne l'image d'un point de l'hyperboloïde dans le disque de
+// Poincaré, par projection depuis le point (-1, 0, 0).
+pub fn to_poincare(point: DVec3) -> DVec2 {
+    // Les points du cône de lumière sont envoyés sur le bord du
+    // disque.
+    if is_ideal(point) {
+        return DVec2::new(point.y, point.z) / point.x;
+    }
+    DVec2::new(point.y, point.z) / (1f64 + point.x)
+}
+
+// Isométrie du modèle de l'hyperboloïde, représentée par une matrice
+// 3x3 conservant la forme de Minkowski.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lorentz {
+    matrix: DMat3,
+}
+
+impl Lorentz {
+    pub fn identity() -> Self {
+        Lorentz {
+            matrix: DMat3::IDENTITY,
+        }
+    }
+    // Retourne la rotation d'angle donné autour de l'axe x0.
+    pub fn rotation(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Lorentz {
+            matrix: DMat3::from_cols(
+                DVec3::X,
+                DVec3::new(0f64, cos, sin),
+                DVec3::new(0f64, -sin, cos),
+            ),
+        }
+    }
+    // Retourne la matrice associée à une isométrie du disque de
+    // Poincaré.
+    pub fn from_mobius(mobius: &Mobius) -> Self {
+        // L'application étant linéaire, elle est déterminée par les
+        // images de trois points non coplanaires avec l'origine de
+        // R³ : le centre du disque et deux points sur les axes.
+        let points = [DVec2::ZERO, DVec2::X / 2f64, DVec2::Y / 2f64];
+        let basis = DMat3::from_cols(
+            from_poincare(points[0]),
+            from_poincare(points[1]),
+            from_poincare(points[2]),
+        );
+        let images = DMat3::from_cols(
+            from_poincare(mobius.apply(points[0])),
+            from_poincare(mobius.apply(points[1])),
+            from_poincare(mobius.apply(points[2])),
+        );
+        Lorentz {
+            matrix: images * basis.inverse(),
+        }
+    }
+    pub fn matrix(&self) -> &DMat3 {
+        &self.matrix
+    }
+    // Retourne l'isométrie qui applique d'abord other, puis self.
+    pub fn compose(&self, other: &Lorentz) -> Self {
+        Lorentz {
+            matrix: self.matrix * other.matrix,
+        }
+    }
+    pub fn inverse(&self) -> Self {
+        // L'inverse d'une matrice de Lorentz M est JMᵀJ, avec J la
+        // matrice de la forme de Minkowski.
+        let j = DMat3::from_diagonal(DVec3::new(1f64, -1f64, -1f64));
+        Lorentz {
+            matrix: j * self.matrix.transpose() * j,
+        }
+    }
+    // Retourne l'image d'un point de l'hyperboloïde. L'image d'un
+    // point idéal est renormalisée par x0 = 1.
+    pub fn apply(&self, point: DVec3) -> DVec3 {
+        let image = self.matrix * point;
+        if is_ideal(image) {
+            image / image.x
+        } else {
+            image
+        }
+    }
+}
+
+// Retourne l'image d'une géodésique du disque sur l'hyperboloïde,
+// donnée par ses extremités : elle est portée par l'intersection de
+// l'hyperboloïde et du plan passant par ces deux points et l'origine
+// de R³.
+pub fn geodesic(geodesic: &Geodesic) -> Option<[DVec3; 2]> {
+    Some([
+        from_poincare(geodesic.start()),
+        from_poincare(geodesic.end()),
+    ])
+}
+
+// Retourne l'isométrie qui envoie le polygone initial sur chaque
+// polygone d'un pavage calculé, dans l'ordre de Tiling::tiles. Si le
+// pavage n'a pas été calculé, on ne retourne rien.
+pub fn transforms(tiling: &Tiling) -> Option<Vec<Lorentz>> {
+    Some(
+        tiling
+            .tiles()?
+            .iter()
+            .map(|tile| Lorentz::from_mobius(tile.transform()))
+            .collect(),
+    )
+}
+
+// Pavage exprimé dans le modèle de l'hyperboloïde.
+pub type Hyperboloid = Model<DVec3, [DVec3; 2]>;
+
+impl Hyperboloid {
+    // Convertit un pavage calculé. Si le pavage n'a pas été calculé,
+    // on ne retourne rien.
+    pub fn new(tiling: &Tiling) -> Option<Self> {
+        Model::convert(tiling, from_poincare, geodesic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{
+        geodesic_passing_by_two_points, hyperbolic_distance,
+    };
+
+    fn points() -> [DVec2; 4] {
+        [
+            DVec2::ZERO,
+            DVec2::new(0.3, -0.2),
+            DVec2::new(-0.7, 0.5),
+            DVec2::new(0.05, 0.9),
+        ]
+    }
+
+    // Les images sont sur l'hyperboloïde, les conversions sont
+    // réciproques l'une de l'autre et conservent la distance
+    // hyperbolique.
+    #[test]
+    fn conversions_are_isometric() {
+        for u in points() {
+            let image = from_poincare(u);
+            assert!(
+                (minkowski_product(image, image) - 1f64).abs() < 1e-9
+            );
+            assert!(image.x > 0f64);
+            assert!(to_poincare(image).distance(u) < 1e-9);
+            for v in points() {
+                let d = distance(image, from_poincare(v));
+                assert!((d - hyperbolic_distance(u, v)).abs() < 1e-9);
+            }
+        }
+    }
+
+    // La matrice associée à une isométrie du disque agit comme elle,
+    // qu'elle renverse ou non l'orientation, et conserve la forme de
+    // Minkowski.
+    #[test]
+    fn lorentz_agrees_with_mobius() {
+        let geodesic = geodesic_passing_by_two_points(
+            DVec2::new(0.1, 0.4),
+            DVec2::new(-0.5, 0.2),
+        )
+        .unwrap();
+        for mobius in [
+            Mobius::rotation(1.2),
+            Mobius::translation(
+                DVec2::new(0.2, 0.1),
+                DVec2::new(-0.4, 0.6),
+            ),
+            Mobius::reflection(&geodesic),
+        ] {
+            let lorentz = Lorentz::from_mobius(&mobius);
+            let identity = lorentz.compose(&lorentz.inverse());
+            assert!(identity
+                .matrix()
+                .abs_diff_eq(DMat3::IDENTITY, 1e-9));
+            for point in points() {
+                let image = lorentz.apply(from_poincare(point));
+                let expected = from_poincare(mobius.apply(point));
+                assert!(image.distance(expected) < 1e-9);
+            }
+        }
+    }
+
+    // L'isométrie de chaque polygone envoie l'origine sur son centre.
+    #[test]
+    fn transforms_place_tiles() {
+        let mut tiling = Tiling::new(7, 3, 3);
+        tiling.compute();
+        let hyperboloid = Hyperboloid::new(&tiling).unwrap();
+        let transforms = transforms(&tiling).unwrap();
+        assert_eq!(transforms.len(), hyperboloid.centers().len());
+        for (transform, &center) in
+            transforms.iter().zip(hyperboloid.centers())
+        {
+            let image = transform.apply(DVec3::X);
+            assert!(image.distance(center) < 1e-6);
+        }
+    }
+}
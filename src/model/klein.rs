@@ -0,0 +1,124 @@
+use glam::DVec2;
+
+use crate::geometry::{Geodesic, Line};
+use crate::model::Model;
+use crate::tiling::Tiling;
+
+// Retourne l'image d'un point du disque de Poincaré dans le modèle de
+// Klein.
+pub fn from_poincare(point: DVec2) -> DVec2 {
+    point * 2f64 / (1f64 + point.length_squared())
+}
+
+// Retourne l'image d'un point du modèle de Klein dans le disque de
+// Poincaré.
+pub fn to_poincare(point: DVec2) -> DVec2 {
+    // On borne le carré de la norme afin que les erreurs d'arrondi ne
+    // nous fassent pas sortir du disque.
+    let norm_squared = point.length_squared().min(1f64);
+    point / (1f64 + (1f64 - norm_squared).sqrt())
+}
+
+// Retourne l'image d'une géodésique dans le modèle de Klein. Les
+// géodésiques y sont des cordes du disque, on obtient donc le segment
+// reliant les images de ses extremités.
+pub fn geodesic(geodesic: &Geodesic) -> Option<Line> {
+    Line::new(
+        from_poincare(geodesic.start()),
+        from_poincare(geodesic.end()),
+    )
+}
+
+// Pavage exprimé dans le modèle de Klein. Les polygones y ont des
+// côtés droits, il suffit donc de relier leurs sommets.
+pub type Klein = Model<DVec2, Line>;
+
+impl Klein {
+    // Convertit un pavage calculé. Si le pavage n'a pas été calculé,
+    // on ne retourne rien.
+    pub fn new(tiling: &Tiling) -> Option<Self> {
+        Model::convert(tiling, from_poincare, geodesic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{
+        geodesic_passing_by_two_points, hyperbolic_distance,
+        hyperbolic_midpoint,
+    };
+
+    // Retourne la distance hyperbolique entre deux points du modèle
+    // de Klein, à partir du birapport de la corde qui les relie.
+    fn klein_distance(u: DVec2, v: DVec2) -> f64 {
+        let product = 1f64 - u.dot(v);
+        let norms = ((1f64 - u.length_squared())
+            * (1f64 - v.length_squared()))
+        .sqrt();
+        (product / norms).acosh()
+    }
+
+    // Les conversions sont réciproques l'une de l'autre et conservent
+    // la distance hyperbolique.
+    #[test]
+    fn conversions_are_isometric() {
+        let points = [
+            DVec2::ZERO,
+            DVec2::new(0.3, -0.2),
+            DVec2::new(-0.7, 0.5),
+            DVec2::new(0.05, 0.9),
+        ];
+        for u in points {
+            assert!(to_poincare(from_poincare(u)).distance(u) < 1e-9);
+            for v in points {
+                let distance = klein_distance(
+                    from_poincare(u),
+                    from_poincare(v),
+                );
+                assert!(
+                    (distance - hyperbolic_distance(u, v)).abs()
+                        < 1e-9
+                );
+            }
+        }
+    }
+
+    // L'image d'une géodésique est une corde : les images de ses
+    // points sont alignées.
+    #[test]
+    fn geodesics_are_chords() {
+        let arc = geodesic_passing_by_two_points(
+            DVec2::new(0.1, 0.4),
+            DVec2::new(-0.5, 0.2),
+        )
+        .unwrap();
+        let midpoint = hyperbolic_midpoint(arc.start(), arc.end());
+        let chord = geodesic(&arc).unwrap();
+        let direction = chord.end() - chord.start();
+        let offset = from_poincare(midpoint) - chord.start();
+        assert!(direction.perp_dot(offset).abs() < 1e-9);
+    }
+
+    // Un pavage n'est converti qu'une fois calculé, et chacun de ses
+    // polygones a alors une image.
+    #[test]
+    fn converts_computed_tilings() {
+        let mut tiling = Tiling::new(7, 3, 3);
+        assert!(Klein::new(&tiling).is_none());
+        tiling.compute();
+        let klein = Klein::new(&tiling).unwrap();
+        let tiles = tiling.tiles().unwrap();
+        assert_eq!(klein.centers().len(), tiles.len());
+        assert_eq!(klein.tiles().len(), tiles.len());
+        assert_eq!(
+            klein.geodesics().len(),
+            tiling.geodesics().unwrap().len()
+        );
+        for (center, tile) in klein.centers().iter().zip(tiles) {
+            assert!(
+                center.distance(from_poincare(tile.center())) < 1e-12
+            );
+        }
+    }
+}
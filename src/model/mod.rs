@@ -0,0 +1,81 @@
+// Conversion des pavages calculés dans le disque de Poincaré vers
+// d'autres modèles du plan hyperbolique.
+pub mod half_plane;
+pub mod hyperboloid;
+pub mod klein;
+pub mod projection;
+
+use glam::DVec2;
+
+use crate::geometry::Geodesic;
+use crate::render::*;
+use crate::tiling::Tiling;
+
+// Pavage exprimé dans un autre modèle du plan hyperbolique : les
+// images du centre et des sommets de chaque polygone, de type P, et
+// celles des géodésiques du pavage, de type G. Chaque modèle en
+// définit un alias et son constructeur (voir klein::Klein,
+// half_plane::HalfPlane et hyperboloid::Hyperboloid).
+#[derive(Debug, Clone)]
+pub struct Model<P, G> {
+    centers: Vec<P>,
+    tiles: Vec<Vec<P>>,
+    geodesics: Vec<G>,
+}
+
+impl<P, G> Model<P, G> {
+    // Convertit un pavage calculé à l'aide des fonctions envoyant un
+    // point et une géodésique du disque dans le modèle, en ignorant
+    // les géodésiques que le modèle ne peut pas représenter. Si le
+    // pavage n'a pas été calculé, on ne retourne rien.
+    pub(crate) fn convert(
+        tiling: &Tiling,
+        point: impl Fn(DVec2) -> P,
+        geodesic: impl Fn(&Geodesic) -> Option<G>,
+    ) -> Option<Self> {
+        let polygon = tiling.polygon()?;
+        let tiles = tiling.tiles()?;
+        Some(Model {
+            centers: tiles
+                .iter()
+                .map(|tile| point(tile.center()))
+                .collect(),
+            tiles: tiles
+                .iter()
+                .map(|tile| {
+                    tile.vertices(polygon)
+                        .into_iter()
+                        .map(&point)
+                        .collect()
+                })
+                .collect(),
+            geodesics: tiling
+                .geodesics()
+                .map(|geodesics| {
+                    geodesics.iter().filter_map(geodesic).collect()
+                })
+                .unwrap_or_default(),
+        })
+    }
+    // Retourne le centre de chaque polygone, dans l'ordre de
+    // Tiling::tiles.
+    pub fn centers(&self) -> &Vec<P> {
+        &self.centers
+    }
+    // Retourne les sommets de chaque polygone, dans l'ordre de
+    // Tiling::tiles.
+    pub fn tiles(&self) -> &Vec<Vec<P>> {
+        &self.tiles
+    }
+    pub fn geodesics(&self) -> &Vec<G> {
+        &self.geodesics
+    }
+}
+
+impl<P, G: Render> Render for Model<P, G> {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        for geodesic in &self.geodesics {
+            geodesic.render(renderer);
+        }
+    }
+}
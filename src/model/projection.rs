@@ -0,0 +1,285 @@
+use std::f64::consts::PI;
+
+use glam::DVec2;
+
+use crate::complex::Complex;
+use crate::geometry::Geodesic;
+use crate::model::half_plane::{self, INFINITY_TOLERANCE};
+use crate::model::klein;
+use crate::render::*;
+
+// Nombre de points par défaut utilisés pour approcher une courbe.
+const DEFAULT_SAMPLES: usize = 32;
+
+// Déclaration de notre trait Projection. Une projection associe à
+// chaque point du disque de Poincaré son image dans un autre modèle
+// du plan hyperbolique. Les courbes sont approchées par des points
+// du disque, dont on calcule les images.
+pub trait Projection {
+    fn project(&self, point: DVec2) -> DVec2;
+    // Retourne les images de points régulièrement répartis sur une
+    // géodésique, de son début à sa fin.
+    fn project_geodesic(
+        &self,
+        geodesic: &Geodesic,
+        samples: usize,
+    ) -> Vec<DVec2> {
+        let points = match geodesic {
+            Geodesic::Diameter(line) => {
+                sample_line(line.start(), line.end(), samples)
+            }
+            Geodesic::Arc(arc) => sample_arc(
+                arc.start(),
+                arc.end(),
+                arc.circle().center(),
+                samples,
+            ),
+        };
+        points
+            .into_iter()
+            .map(|point| self.project(point))
+            .collect()
+    }
+}
+
+// Disque de Poincaré, celui des "Circle Limit" d'Escher. Les points
+// sont laissés tels quels.
+pub struct Disk;
+
+impl Projection for Disk {
+    fn project(&self, point: DVec2) -> DVec2 {
+        point
+    }
+}
+
+// Modèle de Klein. Contrairement aux autres, il n'est pas conforme.
+pub struct KleinDisk;
+
+impl Projection for KleinDisk {
+    fn project(&self, point: DVec2) -> DVec2 {
+        klein::from_poincare(point)
+    }
+}
+
+// Demi-plan de Poincaré, obtenu par la transformation de Cayley.
+pub struct UpperHalfPlane;
+
+impl Projection for UpperHalfPlane {
+    fn project(&self, point: DVec2) -> DVec2 {
+        half_plane::from_poincare(point)
+    }
+}
+
+// Modèle de la bande, obtenu par z -> (4/π) atanh(z). Le disque est
+// envoyé sur la bande infinie -1 < y < 1, les points -1 et 1 du bord
+// étant envoyés à chacune de ses extremités, représentées par une
+// abscisse infinie.
+pub struct Band;
+
+impl Projection for Band {
+    fn project(&self, point: DVec2) -> DVec2 {
+        // Comme pour le demi-plan, on ne divise pas par zéro ni ne
+        // prend le logarithme de zéro aux points -1 et 1.
+        let z: Complex = point.into();
+        if (Complex::ONE - z).norm() < INFINITY_TOLERANCE {
+            return DVec2::new(f64::INFINITY, 0f64);
+        }
+        if (Complex::ONE + z).norm() < INFINITY_TOLERANCE {
+            return DVec2::new(f64::NEG_INFINITY, 0f64);
+        }
+        // atanh(z) = ln((1 + z) / (1 - z)) / 2
+        ((Complex::ONE + z) / (Complex::ONE - z))
+            .ln()
+            .scale(2f64 / PI)
+            .into()
+    }
+}
+
+// Retourne des points régulièrement répartis sur un segment,
+// extremités comprises.
+fn sample_line(
+    start: DVec2,
+    end: DVec2,
+    samples: usize,
+) -> Vec<DVec2> {
+    let samples = samples.max(1);
+    (0..=samples)
+        .map(|i| start.lerp(end, i as f64 / samples as f64))
+        .collect()
+}
+
+// Retourne des points régulièrement répartis sur le plus petit arc du
+// cercle de centre donné reliant deux points, extremités comprises.
+fn sample_arc(
+    start: DVec2,
+    end: DVec2,
+    center: DVec2,
+    samples: usize,
+) -> Vec<DVec2> {
+    let samples = samples.max(1);
+    let radius = start.distance(center);
+    let start_angle = (start - center).y.atan2((start - center).x);
+    let angle = (start - center).angle_between(end - center);
+    (0..=samples)
+        .map(|i| {
+            let angle =
+                start_angle + angle * i as f64 / samples as f64;
+            center + DVec2::new(angle.cos(), angle.sin()) * radius
+        })
+        .collect()
+}
+
+// Retourne des points régulièrement répartis sur un cercle complet,
+// le premier point étant répété à la fin.
+fn sample_circle(
+    center: DVec2,
+    radius: f64,
+    samples: usize,
+) -> Vec<DVec2> {
+    let samples = samples.max(3);
+    (0..=samples)
+        .map(|i| {
+            let angle = 2f64 * PI * i as f64 / samples as f64;
+            center + DVec2::new(angle.cos(), angle.sin()) * radius
+        })
+        .collect()
+}
+
+// Moteur de rendu dessinant à travers une projection. Chaque
+// primitive du disque de Poincaré est approchée par des points dont
+// les images sont reliées par des segments dans le moteur de rendu
+// sous-jacent.
+pub struct ProjectedRenderer<'a, P: Projection> {
+    renderer: &'a mut dyn Renderer,
+    projection: P,
+    samples: usize,
+}
+
+impl<'a, P: Projection> ProjectedRenderer<'a, P> {
+    pub fn new(
+        renderer: &'a mut dyn Renderer,
+        projection: P,
+    ) -> Self {
+        ProjectedRenderer {
+            renderer,
+            projection,
+            samples: DEFAULT_SAMPLES,
+        }
+    }
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+    // Nombre de segments utilisés pour approcher chaque courbe.
+    pub fn set_samples(&mut self, samples: usize) {
+        self.samples = samples;
+    }
+    // Dessine la ligne brisée reliant les images de points. Les
+    // segments dont une extremité est envoyée à l'infini ne peuvent
+    // pas être dessinés et sont ignorés.
+    fn polyline(&mut self, points: Vec<DVec2>) {
+        let points: Vec<DVec2> = points
+            .into_iter()
+            .map(|point| self.projection.project(point))
+            .collect();
+        for segment in points.windows(2) {
+            if segment[0].is_finite() && segment[1].is_finite() {
+                self.renderer.line(segment[0], segment[1]);
+            }
+        }
+    }
+}
+
+impl<'a, P: Projection> Renderer for ProjectedRenderer<'a, P> {
+    fn line(&mut self, start: DVec2, end: DVec2) {
+        self.polyline(sample_line(start, end, self.samples));
+    }
+    fn arc(&mut self, start: DVec2, end: DVec2, center: DVec2) {
+        self.polyline(sample_arc(start, end, center, self.samples));
+    }
+    fn circle(&mut self, center: DVec2, radius: f64) {
+        self.polyline(sample_circle(center, radius, self.samples));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::geodesic_passing_by_two_points;
+    use crate::tiling::{Order, Tiling};
+
+    // Moteur de rendu retenant les segments qu'on lui demande de
+    // dessiner.
+    #[derive(Default)]
+    struct Segments(Vec<(DVec2, DVec2)>);
+
+    impl Renderer for Segments {
+        fn line(&mut self, start: DVec2, end: DVec2) {
+            self.0.push((start, end));
+        }
+        fn arc(&mut self, _: DVec2, _: DVec2, _: DVec2) {}
+        fn circle(&mut self, _: DVec2, _: f64) {}
+    }
+
+    // L'image du disque reste dans la bande -1 < y < 1.
+    #[test]
+    fn band_stays_within_strip() {
+        for i in 0..32 {
+            let angle = 2f64 * PI * i as f64 / 32f64;
+            for radius in [0f64, 0.3, 0.9, 0.999999] {
+                let point =
+                    DVec2::new(angle.cos(), angle.sin()) * radius;
+                let image = Band.project(point);
+                assert!(image.is_finite());
+                assert!(image.y.abs() < 1f64);
+            }
+        }
+    }
+
+    // Les points -1 et 1 sont envoyés aux extremités de la bande, et
+    // les segments les atteignant ne sont pas dessinés.
+    #[test]
+    fn band_sends_boundary_points_to_infinity() {
+        assert_eq!(
+            Band.project(DVec2::X),
+            DVec2::new(f64::INFINITY, 0f64)
+        );
+        assert_eq!(
+            Band.project(-DVec2::X),
+            DVec2::new(f64::NEG_INFINITY, 0f64)
+        );
+        let mut segments = Segments::default();
+        ProjectedRenderer::new(&mut segments, Band)
+            .line(DVec2::ZERO, DVec2::X);
+        assert_eq!(segments.0.len(), DEFAULT_SAMPLES - 1);
+        // Le sommet 0 de chaque polygone idéal est le point 1.
+        let mut tiling = Tiling::new(5, Order::Infinite, 2);
+        tiling.compute();
+        let mut renderer =
+            ProjectedRenderer::new(&mut segments, Band);
+        for geodesic in tiling.geodesics().unwrap() {
+            geodesic.render(&mut renderer);
+        }
+        for (start, end) in segments.0 {
+            assert!(start.is_finite() && end.is_finite());
+        }
+    }
+
+    // Les diamètres réel et imaginaire, qui passent par l'origine et
+    // sont des axes de symétrie de la bande, sont envoyés sur des
+    // droites : l'axe de la bande et le segment x = 0.
+    #[test]
+    fn band_straightens_axes() {
+        for (end, axis) in
+            [(DVec2::X, DVec2::X), (DVec2::Y, DVec2::Y)]
+        {
+            let diameter = geodesic_passing_by_two_points(
+                -end * 0.99,
+                end * 0.99,
+            )
+            .unwrap();
+            for image in Band.project_geodesic(&diameter, 16) {
+                assert!(image.perp_dot(axis).abs() < 1e-9);
+            }
+        }
+    }
+}
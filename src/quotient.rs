@@ -0,0 +1,781 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::geometry::{geodesic_passing_by_two_points, Geodesic};
+use crate::render::*;
+use crate::tiling::{TileId, Tiling};
+use crate::triangle::is_hyperbolic;
+
+// Nombre maximal de classes créées lors de l'énumération. Au-delà, on
+// considère que le groupe quotient est infini ou trop grand.
+const MAX_COSETS: usize = 1 << 20;
+
+// Côtés du triangle fondamental d'un pavage {p,q} (voir
+// triangle::fundamental_triangle avec r = 2) : A est le centre du
+// polygone initial, B son sommet 0 et C le milieu de son côté 0. Les
+// réflexions par rapport à ces côtés engendrent le groupe de
+// symétries du pavage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mirror {
+    AB,
+    AC,
+    BC,
+}
+
+impl Mirror {
+    pub(crate) fn index(self) -> usize {
+        match self {
+            Mirror::AB => 0,
+            Mirror::AC => 1,
+            Mirror::BC => 2,
+        }
+    }
+}
+
+// Table des classes en cours d'énumération (algorithme de
+// Todd-Coxeter, stratégie HLT). Les générateurs étant des
+// involutions, chaque lien est à double sens.
+struct Enumeration {
+    table: Vec<[Option<usize>; 3]>,
+    // Classe dans laquelle a été fusionnée chaque classe. Une classe
+    // est vivante si elle est son propre représentant.
+    parents: Vec<usize>,
+}
+
+impl Enumeration {
+    fn find(&self, mut coset: usize) -> usize {
+        while self.parents[coset] != coset {
+            coset = self.parents[coset];
+        }
+        coset
+    }
+    fn is_alive(&self, coset: usize) -> bool {
+        self.parents[coset] == coset
+    }
+    // Crée une nouvelle classe, image d'une classe par un générateur.
+    fn define(&mut self, coset: usize, generator: usize) -> usize {
+        let new = self.table.len();
+        self.table.push([None; 3]);
+        self.parents.push(new);
+        self.table[coset][generator] = Some(new);
+        self.table[new][generator] = Some(coset);
+        new
+    }
+    fn merge(&mut self, a: usize, b: usize, dead: &mut Vec<usize>) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            // On garde la plus petite classe, afin que la classe 0
+            // (le sous-groupe lui-même) reste vivante.
+            self.parents[a.max(b)] = a.min(b);
+            dead.push(a.max(b));
+        }
+    }
+    // Identifie deux classes, puis toutes les classes qui doivent
+    // l'être en conséquence.
+    fn coincidence(&mut self, a: usize, b: usize) {
+        let mut dead = vec![];
+        self.merge(a, b, &mut dead);
+        let mut i = 0;
+        while i < dead.len() {
+            let coset = dead[i];
+            i += 1;
+            for generator in 0..3 {
+                let other = match self.table[coset][generator] {
+                    Some(other) => other,
+                    None => continue,
+                };
+                if self.table[other][generator] == Some(coset) {
+                    self.table[other][generator] = None;
+                }
+                let (mu, nu) = (self.find(coset), self.find(other));
+                if let Some(image) = self.table[mu][generator] {
+                    self.merge(nu, image, &mut dead);
+                } else if let Some(image) = self.table[nu][generator]
+                {
+                    self.merge(mu, image, &mut dead);
+                } else {
+                    self.table[mu][generator] = Some(nu);
+                    self.table[nu][generator] = Some(mu);
+                }
+            }
+        }
+    }
+    // Parcourt un relateur depuis une classe, dans les deux sens, en
+    // créant les classes manquantes. Le relateur doit ramener à la
+    // classe de départ.
+    fn scan_and_fill(&mut self, coset: usize, relator: &[usize]) {
+        let (mut forward, mut i) = (coset, 0);
+        let (mut backward, mut j) = (coset, relator.len());
+        loop {
+            while i < j {
+                match self.table[forward][relator[i]] {
+                    Some(next) => forward = next,
+                    None => break,
+                }
+                i += 1;
+            }
+            if i == j {
+                if forward != backward {
+                    self.coincidence(forward, backward);
+                }
+                return;
+            }
+            while j > i {
+                match self.table[backward][relator[j - 1]] {
+                    Some(next) => backward = next,
+                    None => break,
+                }
+                j -= 1;
+            }
+            if j == i {
+                self.coincidence(forward, backward);
+                return;
+            }
+            if j == i + 1 {
+                // Une seule lettre manque : on en déduit un lien.
+                self.table[forward][relator[i]] = Some(backward);
+                self.table[backward][relator[i]] = Some(forward);
+                return;
+            }
+            self.define(forward, relator[i]);
+        }
+    }
+}
+
+// Énumère les classes à droite d'un sous-groupe, donné par ses
+// générateurs, dans le quotient du groupe de symétries du pavage {p,q}
+// par des relateurs. Retourne le produit de chaque classe par chaque
+// réflexion (voir Mirror::index) ; la classe 0 est le sous-groupe. Ne
+// retourne rien si le pavage n'est pas hyperbolique ou s'il y a trop
+// de classes.
+pub(crate) fn cosets(
+    p: u8,
+    q: u8,
+    relators: &[Vec<Mirror>],
+    subgroup: &[Vec<Mirror>],
+) -> Option<Vec<[usize; 3]>> {
+    if !is_hyperbolic(p, q, 2) {
+        return None;
+    }
+    let (a, b, c) = (0, 1, 2);
+    let indices = |words: &[Vec<Mirror>]| -> Vec<Vec<usize>> {
+        words
+            .iter()
+            .map(|word| {
+                word.iter().map(|mirror| mirror.index()).collect()
+            })
+            .collect()
+    };
+    let mut words = vec![
+        [a, b].repeat(p.into()),
+        [a, c].repeat(q.into()),
+        [b, c].repeat(2),
+    ];
+    words.extend(indices(relators));
+    let mut enumeration = Enumeration {
+        table: vec![[None; 3]],
+        parents: vec![0],
+    };
+    // Les générateurs du sous-groupe fixent la classe 0.
+    for generator in indices(subgroup) {
+        enumeration.scan_and_fill(0, &generator);
+    }
+    let mut coset = 0;
+    while coset < enumeration.table.len() {
+        for word in &words {
+            if !enumeration.is_alive(coset) {
+                break;
+            }
+            enumeration.scan_and_fill(coset, word);
+        }
+        for generator in 0..3 {
+            if enumeration.is_alive(coset)
+                && enumeration.table[coset][generator].is_none()
+            {
+                enumeration.define(coset, generator);
+            }
+        }
+        if enumeration.table.len() > MAX_COSETS {
+            return None;
+        }
+        coset += 1;
+    }
+    // On renumérote les classes vivantes.
+    let alive: Vec<usize> = (0..enumeration.table.len())
+        .filter(|&coset| enumeration.is_alive(coset))
+        .collect();
+    let indices: HashMap<usize, usize> = alive
+        .iter()
+        .enumerate()
+        .map(|(index, &coset)| (coset, index))
+        .collect();
+    let mut table = Vec::with_capacity(alive.len());
+    for &coset in &alive {
+        let mut row = [0; 3];
+        for (generator, image) in row.iter_mut().enumerate() {
+            let next = enumeration.table[coset][generator]?;
+            *image = indices[&enumeration.find(next)];
+        }
+        table.push(row);
+    }
+    Some(table)
+}
+
+// Retourne le produit d'une classe par la rotation ba, d'angle 2π/p,
+// répétée autant de fois que l'indice d'un côté : elle envoie le côté
+// 0 du polygone initial sur ce côté.
+fn rotate(table: &[[usize; 3]], coset: usize, side: usize) -> usize {
+    (0..side).fold(coset, |coset, _| table[table[coset][1]][0])
+}
+
+// Retourne le produit d'une classe par la réflexion par rapport au
+// côté d'indice donné du polygone initial, qui vaut (ba)^i c (ab)^i.
+pub(crate) fn reflect_side(
+    table: &[[usize; 3]],
+    coset: usize,
+    side: usize,
+) -> usize {
+    let coset = table[rotate(table, coset, side)][2];
+    (0..side).fold(coset, |coset, _| table[table[coset][0]][1])
+}
+
+// Retourne la classe Hg de chaque polygone d'un pavage calculé, g
+// étant l'isométrie envoyant le polygone initial sur celui-ci :
+// chaque polygone est l'image de son parent par la réflexion par
+// rapport à l'un de ses côtés. Les polygones du pavage dual, hormis
+// le premier, n'ont pas de parent : on ne retourne alors rien.
+pub(crate) fn classes(
+    tiling: &Tiling,
+    table: &[[usize; 3]],
+) -> Option<Vec<usize>> {
+    let tiles = tiling.tiles()?;
+    let mut classes: Vec<usize> = Vec::with_capacity(tiles.len());
+    for tile in tiles {
+        classes.push(match (tile.parent(), tile.edge()) {
+            (Some(parent), Some(edge)) => {
+                reflect_side(table, classes[parent], edge)
+            }
+            (None, None) if classes.is_empty() => 0,
+            _ => return None,
+        });
+    }
+    Some(classes)
+}
+
+// Numérote les orbites des éléments sous l'action (à droite) du
+// sous-groupe engendré par deux générateurs. Retourne l'orbite de
+// chaque élément et la taille de chaque orbite.
+pub(crate) fn orbits(
+    table: &[[usize; 3]],
+    generators: [usize; 2],
+) -> (Vec<usize>, Vec<usize>) {
+    let mut labels = vec![usize::MAX; table.len()];
+    let mut sizes = vec![];
+    for start in 0..table.len() {
+        if labels[start] != usize::MAX {
+            continue;
+        }
+        let label = sizes.len();
+        labels[start] = label;
+        let mut size = 1;
+        let mut queue = VecDeque::from([start]);
+        while let Some(element) = queue.pop_front() {
+            for generator in generators {
+                let next = table[element][generator];
+                if labels[next] == usize::MAX {
+                    labels[next] = label;
+                    size += 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+        sizes.push(size);
+    }
+    (labels, sizes)
+}
+
+// Surface compacte obtenue en quotientant un pavage {p,q} par un
+// sous-groupe distingué sans torsion de son groupe de symétries. Ce
+// sous-groupe est donné par des relateurs : des mots dans les
+// réflexions par rapport aux côtés du triangle fondamental qui
+// deviennent l'identité. Le groupe quotient, fini, agit sur la
+// surface pavée par ses faces, arêtes et sommets.
+#[derive(Debug, Clone)]
+pub struct Quotient {
+    p: u8,
+    q: u8,
+    // Produit de chaque élément du groupe quotient par chaque
+    // réflexion (voir Mirror::index), à droite. L'élément 0 est
+    // l'identité.
+    table: Vec<[usize; 3]>,
+    // Produit de chaque élément par la réflexion par rapport à
+    // chaque côté du polygone initial, dans l'ordre de
+    // Tiling::neighbours.
+    sides: Vec<Vec<usize>>,
+    // Face, arête et sommet de la surface sur lesquels chaque élément
+    // envoie le polygone initial, son côté 0 et son sommet 0.
+    faces: Vec<usize>,
+    edges: Vec<usize>,
+    vertices: Vec<usize>,
+    // Un élément envoyant le polygone initial sur chaque face, et son
+    // côté 0 sur chaque arête.
+    face_elements: Vec<usize>,
+    edge_elements: Vec<usize>,
+    vertex_count: usize,
+    orientable: bool,
+}
+
+impl Quotient {
+    // Énumère le groupe quotient du pavage {p,q} par les relateurs
+    // donnés. Ne retourne rien si le pavage n'est pas hyperbolique, si
+    // le quotient est trop grand, ou si le sous-groupe a de la
+    // torsion (le quotient n'est alors pas une surface pavée par des
+    // p-gones).
+    pub fn new(
+        p: u8,
+        q: u8,
+        relators: &[Vec<Mirror>],
+    ) -> Option<Self> {
+        let (a, b, c) = (0, 1, 2);
+        let table = cosets(p, q, relators, &[])?;
+        // Les stabilisateurs du centre, du milieu du côté 0 et du
+        // sommet 0 du polygone initial sont d'ordres 2p, 4 et 2q. Le
+        // sous-groupe est sans torsion si et seulement s'ils
+        // s'injectent dans le quotient.
+        let (faces, face_sizes) = orbits(&table, [a, b]);
+        let (edges, edge_sizes) = orbits(&table, [b, c]);
+        let (vertices, vertex_sizes) = orbits(&table, [a, c]);
+        if face_sizes.iter().any(|&size| size != 2 * p as usize)
+            || edge_sizes.iter().any(|&size| size != 4)
+            || vertex_sizes.iter().any(|&size| size != 2 * q as usize)
+        {
+            return None;
+        }
+        let mut face_elements = vec![usize::MAX; face_sizes.len()];
+        let mut edge_elements = vec![usize::MAX; edge_sizes.len()];
+        for element in (0..table.len()).rev() {
+            face_elements[faces[element]] = element;
+            edge_elements[edges[element]] = element;
+        }
+        // La surface est orientable si la parité de la longueur des
+        // mots est bien définie dans le quotient.
+        let mut parities = vec![None; table.len()];
+        parities[0] = Some(false);
+        let mut orientable = true;
+        let mut queue = VecDeque::from([0]);
+        while let Some(element) = queue.pop_front() {
+            let parity = parities[element] == Some(true);
+            for &next in &table[element] {
+                match parities[next] {
+                    None => {
+                        parities[next] = Some(!parity);
+                        queue.push_back(next);
+                    }
+                    Some(other) if other == parity => {
+                        orientable = false;
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        let mut quotient = Quotient {
+            p,
+            q,
+            table,
+            sides: vec![],
+            faces,
+            edges,
+            vertices,
+            face_elements,
+            edge_elements,
+            vertex_count: vertex_sizes.len(),
+            orientable,
+        };
+        quotient.sides = (0..quotient.table.len())
+            .map(|element| {
+                (0..p as usize)
+                    .map(|side| {
+                        reflect_side(&quotient.table, element, side)
+                    })
+                    .collect()
+            })
+            .collect();
+        Some(quotient)
+    }
+    // Quartique de Klein : surface de genre 3 pavée par 24
+    // heptagones, dont le groupe de symétries est d'ordre 336.
+    pub fn klein_quartic() -> Self {
+        // Ses polygones de Petrie, de mot abc, sont de longueur 8.
+        let relator = [Mirror::AB, Mirror::AC, Mirror::BC].repeat(8);
+        Self::new(7, 3, &[relator])
+            .expect("la quartique de Klein est une surface")
+    }
+    // Surface de Bolza : surface de genre 2 pavée par 6 octogones,
+    // dont le groupe de symétries est d'ordre 96.
+    pub fn bolza() -> Self {
+        // Relateur ((ab)^3 ac)^2.
+        let relator = [
+            Mirror::AB,
+            Mirror::AC,
+            Mirror::AB,
+            Mirror::AC,
+            Mirror::AB,
+            Mirror::AC,
+            Mirror::AB,
+            Mirror::BC,
+        ]
+        .repeat(2);
+        Self::new(8, 3, &[relator])
+            .expect("la surface de Bolza est une surface")
+    }
+    pub fn p(&self) -> u8 {
+        self.p
+    }
+    pub fn q(&self) -> u8 {
+        self.q
+    }
+    // Retourne l'ordre du groupe quotient.
+    pub fn order(&self) -> usize {
+        self.table.len()
+    }
+    pub fn face_count(&self) -> usize {
+        self.face_elements.len()
+    }
+    pub fn edge_count(&self) -> usize {
+        self.edge_elements.len()
+    }
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+    pub fn euler_characteristic(&self) -> i64 {
+        self.vertex_count() as i64 - self.edge_count() as i64
+            + self.face_count() as i64
+    }
+    pub fn is_orientable(&self) -> bool {
+        self.orientable
+    }
+    // Retourne le genre de la surface, si elle est orientable.
+    pub fn genus(&self) -> Option<usize> {
+        if !self.orientable {
+            return None;
+        }
+        Some((2 - self.euler_characteristic()) as usize / 2)
+    }
+    // Retourne le produit d'un élément par une réflexion. Comme les
+    // suivantes, cette fonction ne retourne rien si l'élément ou
+    // l'indice donné n'existe pas.
+    pub fn reflect(
+        &self,
+        element: usize,
+        mirror: Mirror,
+    ) -> Option<usize> {
+        Some(self.table.get(element)?[mirror.index()])
+    }
+    // Retourne le produit d'un élément par la réflexion par rapport
+    // au côté d'indice donné du polygone initial.
+    pub fn reflect_side(
+        &self,
+        element: usize,
+        side: usize,
+    ) -> Option<usize> {
+        self.sides.get(element)?.get(side).copied()
+    }
+    // Retourne l'élément correspondant au mot d'un polygone (voir
+    // Tiling::word).
+    pub fn element(&self, word: &[usize]) -> Option<usize> {
+        word.iter().try_fold(0, |element, &side| {
+            self.reflect_side(element, side)
+        })
+    }
+    // Retourne le produit d'un élément par la rotation envoyant le
+    // côté 0 du polygone initial sur son côté d'indice donné.
+    fn rotate(&self, element: usize, side: usize) -> usize {
+        rotate(&self.table, element, side)
+    }
+    // Retourne la face, l'arête et le sommet sur lesquels un élément
+    // envoie le polygone initial, son côté 0 et son sommet 0.
+    pub fn face(&self, element: usize) -> Option<usize> {
+        self.faces.get(element).copied()
+    }
+    pub fn edge(&self, element: usize) -> Option<usize> {
+        self.edges.get(element).copied()
+    }
+    pub fn vertex(&self, element: usize) -> Option<usize> {
+        self.vertices.get(element).copied()
+    }
+    // Retourne l'arête sur laquelle un élément envoie le côté d'indice
+    // donné du polygone initial.
+    pub fn side_edge(
+        &self,
+        element: usize,
+        side: usize,
+    ) -> Option<usize> {
+        if element >= self.order() || side >= self.p as usize {
+            return None;
+        }
+        Some(self.edges[self.rotate(element, side)])
+    }
+    // Retourne le sommet sur lequel un élément envoie le sommet
+    // d'indice donné du polygone initial.
+    pub fn side_vertex(
+        &self,
+        element: usize,
+        side: usize,
+    ) -> Option<usize> {
+        if element >= self.order() || side >= self.p as usize {
+            return None;
+        }
+        Some(self.vertices[self.rotate(element, side)])
+    }
+    // Retourne les arêtes bordant une face, dans l'ordre de ses
+    // côtés.
+    pub fn face_edges(&self, face: usize) -> Option<Vec<usize>> {
+        let element = *self.face_elements.get(face)?;
+        (0..self.p as usize)
+            .map(|side| self.side_edge(element, side))
+            .collect()
+    }
+    // Retourne les sommets d'une face, dans l'ordre.
+    pub fn face_vertices(&self, face: usize) -> Option<Vec<usize>> {
+        let element = *self.face_elements.get(face)?;
+        (0..self.p as usize)
+            .map(|side| self.side_vertex(element, side))
+            .collect()
+    }
+    // Retourne les deux faces (éventuellement égales) situées de part
+    // et d'autre d'une arête.
+    pub fn edge_faces(&self, edge: usize) -> Option<[usize; 2]> {
+        let element = *self.edge_elements.get(edge)?;
+        Some([
+            self.faces[element],
+            self.faces[self.table[element][Mirror::BC.index()]],
+        ])
+    }
+    // Retourne les deux extrémités (éventuellement égales) d'une
+    // arête.
+    pub fn edge_vertices(&self, edge: usize) -> Option<[usize; 2]> {
+        let element = *self.edge_elements.get(edge)?;
+        Some([
+            self.vertices[element],
+            self.vertices[self.table[element][Mirror::AC.index()]],
+        ])
+    }
+}
+
+// Pavage vu comme un revêtement d'une surface quotient : chaque
+// polygone est étiqueté par la face qu'il recouvre. On en extrait un
+// domaine fondamental connexe, et les paires de côtés de son bord
+// recollés pour obtenir la surface.
+#[derive(Debug, Clone)]
+pub struct Covering {
+    elements: Vec<usize>,
+    faces: Vec<usize>,
+    domain: Vec<TileId>,
+    pairings: Vec<[(TileId, usize); 2]>,
+    boundary: Vec<Geodesic>,
+}
+
+impl Covering {
+    // Étiquette un pavage calculé par un quotient de mêmes p et q. Si
+    // le pavage n'a pas été calculé, ou s'il ne correspond pas au
+    // quotient, on ne retourne rien. Si le pavage n'est pas assez
+    // grand, le domaine fondamental ne contient pas toutes les faces
+    // et certains côtés de son bord restent sans paire.
+    pub fn new(tiling: &Tiling, quotient: &Quotient) -> Option<Self> {
+        if tiling.p() != quotient.p().into()
+            || tiling.q() != quotient.q().into()
+        {
+            return None;
+        }
+        let polygon = tiling.polygon()?;
+        let tiles = tiling.tiles()?;
+        let elements = classes(tiling, &quotient.table)?;
+        let faces: Vec<usize> = elements
+            .iter()
+            .map(|&element| quotient.face(element))
+            .collect::<Option<_>>()?;
+        // On parcourt les polygones en largeur depuis le polygone
+        // initial, en gardant le premier rencontré pour chaque face.
+        let mut covered = vec![false; quotient.face_count()];
+        let mut in_domain = vec![false; tiles.len()];
+        covered[faces[0]] = true;
+        in_domain[0] = true;
+        let mut domain = vec![0];
+        let mut queue = VecDeque::from([0]);
+        while let Some(id) = queue.pop_front() {
+            for &(neighbour, _) in
+                tiling.neighbours(id)?.iter().flatten()
+            {
+                if !covered[faces[neighbour]] {
+                    covered[faces[neighbour]] = true;
+                    in_domain[neighbour] = true;
+                    domain.push(neighbour);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        // Chaque arête de la surface borde deux faces. Si ses deux
+        // côtés ne sont pas adjacents dans le domaine, ils sont sur
+        // son bord et doivent être recollés.
+        let mut sides: HashMap<usize, (TileId, usize)> =
+            HashMap::new();
+        let mut pairings = vec![];
+        let mut boundary = vec![];
+        for &id in &domain {
+            let vertices = tiles[id].vertices(polygon);
+            for (side, neighbour) in
+                tiling.neighbours(id)?.iter().enumerate()
+            {
+                if let Some((neighbour, _)) = neighbour {
+                    if in_domain[*neighbour] {
+                        continue;
+                    }
+                }
+                let edge = quotient.side_edge(elements[id], side)?;
+                if let Some(other) = sides.remove(&edge) {
+                    pairings.push([other, (id, side)]);
+                } else {
+                    sides.insert(edge, (id, side));
+                }
+                boundary.extend(geodesic_passing_by_two_points(
+                    vertices[side],
+                    vertices[(side + 1) % vertices.len()],
+                ));
+            }
+        }
+        Some(Covering {
+            elements,
+            faces,
+            domain,
+            pairings,
+            boundary,
+        })
+    }
+    // Retourne l'élément du groupe quotient associé à chaque polygone,
+    // dans l'ordre de Tiling::tiles.
+    pub fn elements(&self) -> &Vec<usize> {
+        &self.elements
+    }
+    // Retourne la face de la surface recouverte par chaque polygone,
+    // dans l'ordre de Tiling::tiles.
+    pub fn faces(&self) -> &Vec<usize> {
+        &self.faces
+    }
+    // Retourne les polygones du domaine fondamental, un par face.
+    pub fn domain(&self) -> &Vec<TileId> {
+        &self.domain
+    }
+    // Retourne les paires de côtés (polygone et indice du côté) du
+    // bord du domaine fondamental recollées entre elles.
+    pub fn pairings(&self) -> &Vec<[(TileId, usize); 2]> {
+        &self.pairings
+    }
+    // Retourne les côtés du bord du domaine fondamental.
+    pub fn boundary(&self) -> &Vec<Geodesic> {
+        &self.boundary
+    }
+}
+
+impl Render for Covering {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        for geodesic in &self.boundary {
+            geodesic.render(renderer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn klein_quartic() {
+        let quotient = Quotient::klein_quartic();
+        assert_eq!(quotient.order(), 336);
+        assert_eq!(quotient.face_count(), 24);
+        assert_eq!(quotient.edge_count(), 84);
+        assert_eq!(quotient.vertex_count(), 56);
+        assert!(quotient.is_orientable());
+        assert_eq!(quotient.genus(), Some(3));
+    }
+
+    #[test]
+    fn bolza() {
+        let quotient = Quotient::bolza();
+        assert_eq!(quotient.order(), 96);
+        assert_eq!(quotient.face_count(), 6);
+        assert_eq!(quotient.edge_count(), 24);
+        assert_eq!(quotient.vertex_count(), 16);
+        assert_eq!(quotient.genus(), Some(2));
+    }
+
+    // Un sous-groupe avec torsion ne donne pas de surface : le
+    // relateur AB·AC tue la rotation autour du centre du polygone
+    // initial, qui est alors replié sur lui-même.
+    #[test]
+    fn torsion_is_rejected() {
+        assert!(Quotient::new(7, 3, &[vec![Mirror::AB, Mirror::AC]])
+            .is_none());
+    }
+
+    // Les indices hors limites ne font pas paniquer.
+    #[test]
+    fn out_of_range_indices() {
+        let quotient = Quotient::klein_quartic();
+        assert_eq!(quotient.face(336), None);
+        assert_eq!(quotient.reflect(336, Mirror::AB), None);
+        assert_eq!(quotient.reflect_side(0, 7), None);
+        assert_eq!(quotient.side_edge(0, 7), None);
+        assert_eq!(quotient.face_edges(24), None);
+        assert_eq!(quotient.element(&[0, 7]), None);
+    }
+
+    // Le domaine fondamental contient une fois chaque face, et chaque
+    // côté de son bord est apparié à exactement un autre côté,
+    // recouvrant la même arête de la surface.
+    #[test]
+    fn covering_pairs_boundary_sides_once() {
+        for (quotient, max_depth) in
+            [(Quotient::klein_quartic(), 6), (Quotient::bolza(), 4)]
+        {
+            let mut tiling =
+                Tiling::new(quotient.p(), quotient.q(), max_depth);
+            tiling.compute();
+            let covering = Covering::new(&tiling, &quotient).unwrap();
+            let domain: HashSet<TileId> =
+                covering.domain().iter().copied().collect();
+            assert_eq!(domain.len(), quotient.face_count());
+            let faces: HashSet<usize> = covering
+                .domain()
+                .iter()
+                .map(|&id| covering.faces()[id])
+                .collect();
+            assert_eq!(faces.len(), quotient.face_count());
+            let mut boundary = HashSet::new();
+            for &id in covering.domain() {
+                for (side, neighbour) in
+                    tiling.neighbours(id).unwrap().iter().enumerate()
+                {
+                    match neighbour {
+                        Some((other, _))
+                            if domain.contains(other) => {}
+                        _ => {
+                            boundary.insert((id, side));
+                        }
+                    }
+                }
+            }
+            let mut paired = HashSet::new();
+            for &[a, b] in covering.pairings() {
+                assert!(paired.insert(a) && paired.insert(b));
+                let edge = |(id, side): (TileId, usize)| {
+                    quotient.side_edge(covering.elements()[id], side)
+                };
+                assert_eq!(edge(a), edge(b));
+            }
+            assert_eq!(paired, boundary);
+            assert_eq!(covering.boundary().len(), boundary.len());
+        }
+    }
+}
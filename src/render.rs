@@ -0,0 +1,20 @@
+use glam::DVec2;
+
+// Déclaration de notre trait Renderer. Il représente l'ensemble des
+// primitives que doit fournir un moteur de rendu (Nannou, SVG, image
+// matricielle...) afin de dessiner nos structures.
+pub trait Renderer {
+    // Dessine le segment reliant deux points.
+    fn line(&mut self, start: DVec2, end: DVec2);
+    // Dessine le plus petit arc du cercle de centre donné reliant
+    // deux points.
+    fn arc(&mut self, start: DVec2, end: DVec2, center: DVec2);
+    // Dessine un cercle complet.
+    fn circle(&mut self, center: DVec2, radius: f64);
+}
+
+// Déclaration de notre trait Render, implémenté par chaque structure
+// pouvant être dessinée par un moteur de rendu.
+pub trait Render {
+    fn render(&self, renderer: &mut dyn Renderer);
+}
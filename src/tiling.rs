@@ -1,54 +1,309 @@
-use nannou::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::f64::consts::PI;
+use std::fmt;
 
-use crate::{
-    euclidian_distance_from_center_to_vertex,
-    geodesic_passing_by_two_points, reflect::Reflect, Shape,
+use glam::DVec2;
+
+use crate::automaton::Automaton;
+use crate::euclidian_distance_from_center_to_vertex;
+use crate::geometry::{
+    angle_at_vertex, geodesic_passing_by_two_points,
+    hyperbolic_distance, Geodesic,
 };
+use crate::key::{BoundaryMap, PointMap};
+use crate::mobius::Mobius;
+use crate::model::half_plane;
+use crate::triangle::is_hyperbolic;
+
+// Valeur de p (nombre de côtés d'un polygone) ou de q (nombre de
+// polygones autour d'un sommet). Avec q infini, les sommets des
+// polygones sont sur le bord du disque (polygones idéaux). Avec p
+// infini, les polygones sont des apeirogones, dont les sommets sont
+// sur un horocycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Order {
+    Finite(u8),
+    Infinite,
+}
+
+impl Order {
+    // Retourne la valeur de l'ordre s'il est fini.
+    pub fn finite(self) -> Option<u8> {
+        match self {
+            Order::Finite(n) => Some(n),
+            Order::Infinite => None,
+        }
+    }
+    pub fn is_infinite(self) -> bool {
+        self == Order::Infinite
+    }
+}
+
+impl From<u8> for Order {
+    fn from(n: u8) -> Self {
+        Order::Finite(n)
+    }
+}
+
+impl fmt::Display for Order {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Order::Finite(n) => write!(f, "{n}"),
+            Order::Infinite => write!(f, "∞"),
+        }
+    }
+}
+
+// Nombre de côtés d'un apeirogone calculés de part et d'autre de son
+// sommet central, par défaut.
+const DEFAULT_APEIROGON_EDGES: usize = 16;
+// Diamètre en dessous duquel un apeirogone n'est pas calculé, si
+// aucun diamètre minimal n'est donné. Chaque apeirogone ayant de
+// nombreux voisins, leur nombre explose sinon d'une couche à l'autre,
+// alors que seuls les apeirogones d'une taille donnée sont en nombre
+// fini.
+const DEFAULT_APEIROGON_MIN_DIAMETER: f64 = 1f64 / 200f64;
+
+// Retourne π/n, qui vaut 0 si n est infini.
+fn angle(n: Order) -> f64 {
+    match n {
+        Order::Finite(n) => PI / n as f64,
+        Order::Infinite => 0f64,
+    }
+}
+
+// Norme au-delà de laquelle un sommet est considéré comme idéal.
+pub(crate) const IDEAL_NORM: f64 = 1f64 - 1e-9;
+// Nombre maximal de réflexions effectuées par Tiling::locate avant
+// d'abandonner, pour les points trop proches du bord du disque.
+const MAX_LOCATE_STEPS: usize = 1000;
+// Distance hyperbolique en dessous de laquelle un point est considéré
+// comme situé sur un côté.
+const LOCATE_TOLERANCE: f64 = 1e-9;
+
+// Méthode utilisée par Tiling::compute pour obtenir les polygones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    // Parcours des voisins de chaque polygone, ceux déjà atteints
+    // étant reconnus à leur centre.
+    Geometric,
+    // Parcours des mots acceptés par l'automate du pavage (voir
+    // automaton::Automaton) : les polygones ne sont pas comparés à
+    // ceux déjà atteints, mais l'automate est construit à partir des
+    // positions des polygones proches du polygone initial, et les
+    // sommets et côtés restent identifiés par leur position. Le
+    // calcul n'en est donc qu'un peu plus rapide, de l'ordre de 20 %.
+    // L'automate est construit au premier calcul, ce qui peut prendre
+    // plusieurs secondes pour de grands p et q (une vingtaine pour
+    // {20,20}), puis conservé tant que p et q ne changent pas. Les
+    // apeirogones sont toujours pavés géométriquement.
+    Automaton,
+}
+
+// Indice d'un polygone dans le tableau retourné par Tiling::tiles.
+pub type TileId = usize;
+
+// Polygone du pavage. Il est représenté par l'isométrie qui envoie
+// le polygone initial sur lui, ses sommets n'étant calculés qu'à la
+// demande. La couche d'un polygone est sa distance combinatoire au
+// polygone initial, c'est-à-dire le nombre minimal de côtés à
+// traverser pour l'atteindre depuis celui-ci. Les polygones du pavage
+// dual n'étant pas calculés couche par couche, ils sont tous dans la
+// couche 0 (voir Tiling::dual).
+#[derive(Debug, Clone)]
+pub struct Tile {
+    transform: Mobius,
+    layer: u8,
+    parent: Option<TileId>,
+    edge: Option<usize>,
+}
+
+impl Tile {
+    pub fn transform(&self) -> &Mobius {
+        &self.transform
+    }
+    // Retourne l'image de l'origine. Pour un apeirogone, dont le
+    // centre est idéal, il s'agit seulement d'un point intérieur.
+    pub fn center(&self) -> DVec2 {
+        self.transform.apply(DVec2::ZERO)
+    }
+    // Retourne les sommets du polygone, à partir de ceux du polygone
+    // initial (voir Tiling::polygon).
+    pub fn vertices(&self, polygon: &[DVec2]) -> Vec<DVec2> {
+        polygon
+            .iter()
+            .map(|&vertex| self.transform.apply(vertex))
+            .collect()
+    }
+    pub fn layer(&self) -> u8 {
+        self.layer
+    }
+    // Retourne le polygone de la couche précédente duquel celui-ci a
+    // été obtenu par réflexion. Le polygone initial n'en a pas.
+    pub fn parent(&self) -> Option<TileId> {
+        self.parent
+    }
+    // Retourne l'indice du côté du parent qu'il a fallu traverser pour
+    // atteindre ce polygone, c'est-à-dire la dernière lettre de son
+    // mot (voir Tiling::word).
+    pub fn edge(&self) -> Option<usize> {
+        self.edge
+    }
+    // Retourne le diamètre euclidien du polygone, soit la plus grande
+    // distance entre deux de ses sommets.
+    pub fn diameter(&self, polygon: &[DVec2]) -> f64 {
+        let vertices = self.vertices(polygon);
+        let mut diameter = 0f64;
+        for (i, a) in vertices.iter().enumerate() {
+            for b in &vertices[i + 1..] {
+                diameter = diameter.max(a.distance(*b));
+            }
+        }
+        diameter
+    }
+    // Retourne l'aire hyperbolique du polygone, calculée à partir de
+    // ses angles : un polygone à p côtés a pour aire (p - 2)π moins la
+    // somme de ses angles. Les angles aux sommets idéaux sont nuls.
+    // Elle n'a pas de sens pour un apeirogone.
+    pub fn area(&self, polygon: &[DVec2]) -> f64 {
+        let vertices = self.vertices(polygon);
+        let n = vertices.len();
+        let angles: f64 = (0..n)
+            .filter(|&i| vertices[i].length() < IDEAL_NORM)
+            .map(|i| {
+                angle_at_vertex(
+                    vertices[(i + n - 1) % n],
+                    vertices[i],
+                    vertices[(i + 1) % n],
+                )
+            })
+            .sum();
+        (n as f64 - 2f64) * PI - angles
+    }
+}
 
 pub struct Tiling {
-    p: u8,
-    q: u8,
+    p: Order,
+    q: Order,
     max_depth: u8,
-    centers: Vec<Point2>,
-    geodesics: Vec<Box<dyn Reflect>>,
-    shapes: Vec<Shape>,
+    // Conditions d'arrêt facultatives, s'ajoutant à la profondeur
+    // maximale.
+    min_diameter: Option<f64>,
+    max_radius: Option<f64>,
+    max_tiles: Option<usize>,
+    centers: Vec<DVec2>,
+    geodesics: Vec<Geodesic>,
+    tiles: Vec<Tile>,
+    // Polygone initial, ses côtés et les réflexions par rapport à
+    // chacun d'eux.
+    polygon: Vec<DVec2>,
+    sides: Vec<Geodesic>,
+    reflections: Vec<Mobius>,
+    // Indice du polygone associé à chaque centre, ainsi qu'à chaque
+    // centre idéal pour les apeirogones.
+    tile_ids: PointMap<TileId>,
+    ideal_tile_ids: BoundaryMap<TileId>,
+    // Indices des sommets et des côtés déjà rencontrés. Les sommets
+    // idéaux ont leur propre table.
+    vertices: PointMap<usize>,
+    ideal_vertices: BoundaryMap<usize>,
+    edges: HashSet<(usize, usize)>,
+    // Indices des sommets de chaque polygone, dans l'ordre de
+    // Tile::vertices.
+    tile_vertices: Vec<Vec<usize>>,
+    // Voisin de chaque polygone de l'autre côté de chacun de ses
+    // côtés, avec l'indice de ce côté chez le voisin.
+    neighbours: Vec<Vec<Option<(TileId, usize)>>>,
+    // Polygones entourant chaque sommet, dans l'ordre. Le tableau est
+    // vide si les q polygones n'ont pas tous été calculés.
+    vertex_cycles: Vec<Vec<TileId>>,
+    apeirogon_edges: usize,
+    backend: Backend,
+    // Automate du pavage, conservé entre deux calculs tant que p et q
+    // ne changent pas (voir Tiling::automaton).
+    automaton: Option<Automaton>,
     computed: bool,
 }
 
 impl Tiling {
-    pub fn new(p: u8, q: u8, max_depth: u8) -> Self {
+    // Les valeurs finies de p et q peuvent être données directement,
+    // comme dans Tiling::new(7, 3, 6).
+    pub fn new(
+        p: impl Into<Order>,
+        q: impl Into<Order>,
+        max_depth: u8,
+    ) -> Self {
         Tiling {
-            p,
-            q,
+            p: p.into(),
+            q: q.into(),
             max_depth,
-            centers: vec![Point2::ZERO],
+            min_diameter: None,
+            max_radius: None,
+            max_tiles: None,
+            centers: vec![],
             geodesics: vec![],
-            shapes: vec![],
+            tiles: vec![],
+            polygon: vec![],
+            sides: vec![],
+            reflections: vec![],
+            tile_ids: PointMap::new(),
+            ideal_tile_ids: BoundaryMap::new(),
+            vertices: PointMap::new(),
+            ideal_vertices: BoundaryMap::new(),
+            edges: HashSet::new(),
+            tile_vertices: vec![],
+            neighbours: vec![],
+            vertex_cycles: vec![],
+            apeirogon_edges: DEFAULT_APEIROGON_EDGES,
+            backend: Backend::Geometric,
+            automaton: None,
             computed: false,
         }
     }
-    pub fn centers(&self) -> Option<&Vec<Point2>> {
-        // Si le tableau est possède sa valeur d'initialisation
-        // ([Point2::Zero]), on ne retourne rien.
+    pub fn centers(&self) -> Option<&Vec<DVec2>> {
+        // Si le tableau est vide, on ne retourne rien.
         match self.centers.as_slice() {
-            &[v] if v == Point2::ZERO => None,
+            &[] => None,
             _ => Some(&self.centers),
         }
     }
-    pub fn geodesics(&self) -> Option<&Vec<Box<dyn Reflect>>> {
+    pub fn geodesics(&self) -> Option<&Vec<Geodesic>> {
         // Si le tableau est vide, on ne retourne rien.
         match self.geodesics.as_slice() {
             &[] => None,
             _ => Some(&self.geodesics),
         }
     }
-    pub fn shapes(&self) -> Option<&Vec<Shape>> {
+    pub fn tiles(&self) -> Option<&Vec<Tile>> {
         // Si le tableau est vide, on ne retourne rien.
-        match self.shapes.as_slice() {
+        match self.tiles.as_slice() {
             &[] => None,
-            _ => Some(&self.shapes),
+            _ => Some(&self.tiles),
         }
     }
+    // Retourne le polygone initial, centré à l'origine. Chaque
+    // polygone du pavage en est l'image par une isométrie.
+    pub fn polygon(&self) -> Option<&Vec<DVec2>> {
+        // Si le tableau est vide, on ne retourne rien.
+        match self.polygon.as_slice() {
+            &[] => None,
+            _ => Some(&self.polygon),
+        }
+    }
+    // Retourne les réflexions par rapport aux côtés du polygone
+    // initial, dans l'ordre des côtés.
+    pub(crate) fn reflections(&self) -> &[Mobius] {
+        &self.reflections
+    }
+    // Retourne les polygones d'une couche donnée. Les polygones étant
+    // calculés couche par couche, ils sont contigus dans le tableau.
+    pub fn layer(&self, layer: u8) -> &[Tile] {
+        let start =
+            self.tiles.partition_point(|tile| tile.layer < layer);
+        let end =
+            self.tiles.partition_point(|tile| tile.layer <= layer);
+        &self.tiles[start..end]
+    }
     // Calcule les polygones,géodésiques et points du pavage. Cette
     // fonction permet de ne pas faire de calculs inutiles durant
     // l'initialisation du pavage.
@@ -58,104 +313,1043 @@ impl Tiling {
             return;
         }
         // On réinitialise nos variables.
-        self.centers = vec![Point2::ZERO];
+        self.centers = vec![];
         self.geodesics = vec![];
-        self.shapes = vec![];
+        self.tiles = vec![];
+        self.polygon = vec![];
+        self.sides = vec![];
+        self.reflections = vec![];
+        self.tile_ids = PointMap::new();
+        self.ideal_tile_ids = BoundaryMap::new();
+        self.vertices = PointMap::new();
+        self.ideal_vertices = BoundaryMap::new();
+        self.edges = HashSet::new();
+        self.tile_vertices = vec![];
+        self.neighbours = vec![];
+        self.vertex_cycles = vec![];
         self.computed = true;
-        if !self.is_tilable() {
+        if !self.is_tilable() || !self.build_polygon() {
             return;
         }
-        // On crée notre polygone initial.
-        let mut shape = vec![];
-        let radius =
-            euclidian_distance_from_center_to_vertex(self.p, self.q);
-        // On calcule un nombre p de points, uniformément répartis sur
-        // notre cercle de centre (0;0), grâce à de la trigonomètrie.
-        let mut angle = 0f32;
-        let p_as_f32: f32 = self.p.into();
-        for _ in 0..self.p {
-            shape.push(Point2::new(
-                angle.cos() * radius,
-                angle.sin() * radius,
-            ));
-            angle += 2f32 * PI / p_as_f32;
-        }
-        self.shapes.push(shape.clone());
+        self.add_tile(Tile {
+            transform: Mobius::identity(),
+            layer: 0,
+            parent: None,
+            edge: None,
+        });
 
         // On commence à paver
-        self.tile(&shape, Point2::ZERO, 0);
+        match self.automaton() {
+            Some(automaton) => {
+                self.tile_with_automaton(&automaton);
+                self.automaton = Some(automaton);
+            }
+            None => self.tile(),
+        }
+        self.link();
         self.computed = true;
     }
-    // Fonction récursive responsable de calculer les
-    // "sous-polygones".
-    fn tile(
-        &mut self,
-        current_shape: &Shape,
-        current_center: Point2,
-        depth: u8,
-    ) {
-        // Si la profondeur atteint la profondeur maximale, on arrête.
-        if depth < self.max_depth {
-            for i in 0..current_shape.len() {
-                // On prend chaque point du polygone par paire a,b.
-                let a = current_shape[i];
-                let b = current_shape[(i + 1) % current_shape.len()];
-                // On calcule la géodésique passant par les deux
-                // points. Si une valeur est retournée, on reflète
-                // notre forme actuelle dans la géodésique.
-                if let Some(geodesic) =
-                    geodesic_passing_by_two_points(a, b)
+    // Construit le polygone initial, ses côtés et les réflexions
+    // associées. Retourne false si l'un des côtés n'a pas pu être
+    // construit.
+    fn build_polygon(&mut self) -> bool {
+        // On crée notre polygone initial.
+        let shape = match self.p {
+            Order::Infinite => self.apeirogon(),
+            Order::Finite(p) => {
+                let mut shape = vec![];
+                // Les sommets d'un polygone idéal sont sur le bord du
+                // disque.
+                let radius = match self.q {
+                    Order::Infinite => 1f64,
+                    Order::Finite(q) => {
+                        euclidian_distance_from_center_to_vertex(p, q)
+                    }
+                };
+                // On calcule un nombre p de points, uniformément
+                // répartis sur notre cercle de centre (0;0), grâce à
+                // de la trigonomètrie.
+                let mut angle = 0f64;
+                let p_as_f64: f64 = p.into();
+                for _ in 0..p {
+                    shape.push(DVec2::new(
+                        angle.cos() * radius,
+                        angle.sin() * radius,
+                    ));
+                    angle += 2f64 * PI / p_as_f64;
+                }
+                shape
+            }
+        };
+        // Un apeirogone n'est pas fermé : son dernier sommet n'est pas
+        // relié au premier.
+        let edges = if self.p.is_infinite() {
+            shape.len() - 1
+        } else {
+            shape.len()
+        };
+        // On calcule la réflexion par rapport à chaque côté du
+        // polygone initial. En la composant avec l'isométrie d'un
+        // polygone, on obtient son voisin de l'autre côté de ce côté.
+        for i in 0..edges {
+            match geodesic_passing_by_two_points(
+                shape[i],
+                shape[(i + 1) % shape.len()],
+            ) {
+                Some(geodesic) => {
+                    self.reflections.push(geodesic.reflection());
+                    self.sides.push(geodesic);
+                }
+                None => return false,
+            }
+        }
+        self.polygon = shape;
+        true
+    }
+    // Retourne les sommets calculés de l'apeirogone initial. Dans le
+    // demi-plan de Poincaré, ses sommets sont les points k + ih, k
+    // entier, et son centre idéal est le point à l'infini, envoyé sur
+    // le point 1 du disque. La hauteur h est choisie pour que deux
+    // côtés consécutifs forment un angle de 2π/q.
+    fn apeirogon(&self) -> Vec<DVec2> {
+        let height = angle(self.q).tan() / 2f64;
+        let edges = self.apeirogon_edges as i64;
+        (-edges..=edges)
+            .map(|k| {
+                half_plane::to_poincare(DVec2::new(k as f64, height))
+            })
+            .collect()
+    }
+    // Retourne le pavage dual {q,p}, dont les sommets sont les
+    // centres des polygones du pavage et les polygones sont centrés
+    // sur ses sommets. Le polygone dual d'indice i est centré sur le
+    // sommet d'indice i (voir Tiling::vertex_ids), et le sommet dual
+    // d'indice i est le centre du polygone d'indice i. Les polygones
+    // duals n'étant pas ordonnés par distance au premier d'entre eux,
+    // leur couche n'a pas de sens : elle vaut 0 pour tous. On ne
+    // retourne rien si le pavage n'a pas été calculé, ou si p ou q
+    // est infini.
+    pub fn dual(&self) -> Option<Tiling> {
+        self.tiles()?;
+        let p = self.p.finite()?;
+        self.q.finite()?;
+        let mut dual = Tiling::new(self.q, self.p, self.max_depth);
+        dual.min_diameter = self.min_diameter;
+        dual.max_radius = self.max_radius;
+        dual.max_tiles = self.max_tiles;
+        dual.computed = true;
+        if !dual.build_polygon() {
+            return None;
+        }
+        // Les sommets duals reçoivent les indices des polygones.
+        for (id, tile) in self.tiles.iter().enumerate() {
+            dual.vertices.insert(tile.center(), id);
+        }
+        // Pour chaque sommet, on retient le premier polygone auquel il
+        // appartient et sa position dans celui-ci.
+        let mut corners = vec![None; self.vertices.len()];
+        for (id, indices) in self.tile_vertices.iter().enumerate() {
+            for (corner, &vertex) in indices.iter().enumerate() {
+                corners[vertex].get_or_insert((id, corner));
+            }
+        }
+        // Le polygone dual centré sur le premier sommet du polygone
+        // initial s'obtient en le tournant d'un demi-tour, afin que
+        // l'un de ses sommets pointe vers l'origine, puis en le
+        // translatant sur ce sommet. Les deux polygones ayant le même
+        // rayon hyperbolique, ce sommet arrive sur l'origine.
+        let placement =
+            Mobius::translation(DVec2::ZERO, self.polygon[0])
+                .compose(&Mobius::rotation(PI));
+        let p_as_f64: f64 = p.into();
+        let placements: Vec<Mobius> = corners
+            .into_iter()
+            .flatten()
+            .map(|(id, corner)| {
+                let rotation = Mobius::rotation(
+                    2f64 * PI * corner as f64 / p_as_f64,
+                );
+                self.tiles[id]
+                    .transform
+                    .compose(&rotation)
+                    .compose(&placement)
+            })
+            .collect();
+        // Une telle isométrie n'est définie qu'à une symétrie du
+        // polygone dual près. Comme pour les polygones du pavage, on
+        // choisit la composée des réflexions par rapport aux côtés qui
+        // mène au polygone, en la propageant de voisin en voisin depuis
+        // le premier. Si p est pair, deux voisins ont alors des
+        // isométries d'orientations opposées.
+        // Deux polygones duals sont voisins si leurs centres sont
+        // reliés par un côté du pavage.
+        let mut adjacent = vec![vec![]; placements.len()];
+        for indices in &self.tile_vertices {
+            for i in 0..self.reflections.len() {
+                let (u, v) =
+                    (indices[i], indices[(i + 1) % indices.len()]);
+                adjacent[u].push(v);
+                adjacent[v].push(u);
+            }
+        }
+        let mut transforms = vec![None; placements.len()];
+        transforms[0] = Some(placements[0]);
+        let mut queue = VecDeque::from([0]);
+        while let Some(current) = queue.pop_front() {
+            let transform =
+                transforms[current].unwrap_or(placements[current]);
+            for &next in &adjacent[current] {
+                if transforms[next].is_some() {
+                    continue;
+                }
+                // Le voisin est l'image du polygone par la réflexion
+                // par rapport au côté le séparant de son centre.
+                let center = placements[next].apply(DVec2::ZERO);
+                transforms[next] = dual
+                    .reflections
+                    .iter()
+                    .map(|reflection| transform.compose(reflection))
+                    .min_by(|a, b| {
+                        hyperbolic_distance(
+                            a.apply(DVec2::ZERO),
+                            center,
+                        )
+                        .total_cmp(
+                            &hyperbolic_distance(
+                                b.apply(DVec2::ZERO),
+                                center,
+                            ),
+                        )
+                    });
+                queue.push_back(next);
+            }
+        }
+        for (transform, placement) in
+            transforms.into_iter().zip(placements)
+        {
+            dual.add_tile(Tile {
+                transform: transform.unwrap_or(placement),
+                layer: 0,
+                parent: None,
+                edge: None,
+            });
+        }
+        dual.link();
+        Some(dual)
+    }
+    // Retourne l'indice du polygone contenant un point, s'il a été
+    // calculé. Un point hors du disque n'appartient à aucun polygone.
+    // Les apeirogones ne sont pas pris en charge.
+    pub fn locate(&self, point: DVec2) -> Option<TileId> {
+        // Les côtés d'un apeirogone n'étant pas tous calculés, on ne
+        // peut pas s'y ramener.
+        if self.p.is_infinite() || point.length_squared() >= 1f64 {
+            return None;
+        }
+        // Tant que le point est séparé de l'origine par un côté du
+        // polygone initial, on le reflète par rapport à ce côté, ce
+        // qui le rapproche de l'origine. Une fois le point dans le
+        // polygone initial, la composée des réflexions envoie ce
+        // dernier sur le polygone contenant le point de départ.
+        let mut point = point;
+        let mut transform = Mobius::identity();
+        for _ in 0..MAX_LOCATE_STEPS {
+            // On choisit le côté dont le point est le plus éloigné,
+            // un point situé sur le prolongement d'un côté n'étant pas
+            // déplacé par la réflexion correspondante.
+            let side = self
+                .sides
+                .iter()
+                .enumerate()
+                .filter(|(_, side)| {
+                    !side.same_side(DVec2::ZERO, point)
+                })
+                .map(|(i, side)| (i, side.distance(point)))
+                .filter(|&(_, distance)| distance > LOCATE_TOLERANCE)
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(i, _)| i);
+            match side {
+                Some(i) => {
+                    point = self.reflections[i].apply(point);
+                    transform =
+                        transform.compose(&self.reflections[i]);
+                }
+                None => {
+                    return self
+                        .tile_ids
+                        .get(transform.apply(DVec2::ZERO))
+                        .copied();
+                }
+            }
+        }
+        None
+    }
+    // Calcule les "sous-polygones" couche par couche, en partant du
+    // polygone initial. Un polygone est toujours atteint pour la
+    // première fois depuis la couche qui précède la sienne.
+    fn tile(&mut self) {
+        let mut queue = VecDeque::from([0]);
+        while let Some(current) = queue.pop_front() {
+            // Si la couche atteint la profondeur maximale, on ne
+            // calcule pas ses "sous-polygones".
+            let current_tile = self.tiles[current].clone();
+            if current_tile.layer >= self.max_depth {
+                continue;
+            }
+            for i in 0..self.reflections.len() {
+                // Si le nombre maximal de polygones est atteint, on
+                // arrête.
+                if self
+                    .max_tiles
+                    .is_some_and(|max| self.tiles.len() >= max)
+                {
+                    return;
+                }
+                // Le voisin de l'autre côté du i-ème côté s'obtient
+                // en reflétant le polygone initial par rapport à son
+                // i-ème côté, puis en lui appliquant l'isométrie du
+                // polygone actuel.
+                let next_tile = Tile {
+                    transform: current_tile
+                        .transform
+                        .compose(&self.reflections[i]),
+                    layer: current_tile.layer + 1,
+                    parent: Some(current),
+                    edge: Some(i),
+                };
+                // Si le polygone a déjà été atteint, on ne l'ajoute
+                // pas une seconde fois.
+                if self.contains(&next_tile)
+                    || !self.is_within_limits(&next_tile)
                 {
-                    // On reflète chaque point de notre forme actuelle
-                    // dans la géodésique pour composer notre nouvelle
-                    // forme.
-                    let next_center =
-                        geodesic.reflect(current_center);
-                    self.centers.push(next_center);
-                    let mut next_shape = vec![];
-                    for j in 0..current_shape.len() {
-                        let point = current_shape[j];
-                        next_shape.push(geodesic.reflect(point));
+                    continue;
+                }
+                queue.push_back(self.add_tile(next_tile));
+            }
+        }
+    }
+    // Retourne l'automate du pavage, si on l'utilise pour paver. Sa
+    // construction pouvant prendre plusieurs secondes pour de grands p
+    // et q, on réutilise celui du calcul précédent s'il existe.
+    fn automaton(&mut self) -> Option<Automaton> {
+        match self.backend {
+            Backend::Automaton => {
+                self.automaton.take().or_else(|| Automaton::new(self))
+            }
+            Backend::Geometric => None,
+        }
+    }
+    // Calcule les polygones couche par couche comme Tiling::tile, mais
+    // en ne suivant que les côtés acceptés par l'automate : chaque
+    // polygone n'est alors atteint qu'une fois, avec le même mot. Les
+    // descendants d'un polygone écarté ne sont en revanche pas
+    // calculés.
+    fn tile_with_automaton(&mut self, automaton: &Automaton) {
+        let mut queue = VecDeque::from([(0, 0)]);
+        while let Some((current, state)) = queue.pop_front() {
+            let current_tile = self.tiles[current].clone();
+            if current_tile.layer >= self.max_depth {
+                continue;
+            }
+            for i in 0..self.reflections.len() {
+                if self
+                    .max_tiles
+                    .is_some_and(|max| self.tiles.len() >= max)
+                {
+                    return;
+                }
+                // Si le mot n'est pas accepté, le polygone est atteint
+                // par un autre mot.
+                let next_state = match automaton.next(state, i) {
+                    Some(next_state) => next_state,
+                    None => continue,
+                };
+                let next_tile = Tile {
+                    transform: current_tile
+                        .transform
+                        .compose(&self.reflections[i]),
+                    layer: current_tile.layer + 1,
+                    parent: Some(current),
+                    edge: Some(i),
+                };
+                if !self.is_within_limits(&next_tile) {
+                    continue;
+                }
+                queue.push_back((
+                    self.add_tile(next_tile),
+                    next_state,
+                ));
+            }
+        }
+    }
+    // Vérifie si un polygone respecte les conditions d'arrêt portant
+    // sur sa position et sa taille.
+    fn is_within_limits(&self, tile: &Tile) -> bool {
+        // Si le centre du polygone est trop éloigné de l'origine, ou
+        // si le polygone est trop petit, on ne l'ajoute pas.
+        let min_diameter = self.min_diameter.or(self
+            .p
+            .is_infinite()
+            .then_some(DEFAULT_APEIROGON_MIN_DIAMETER));
+        !self
+            .max_radius
+            .is_some_and(|max| tile.center().length() > max)
+            && !min_diameter
+                .is_some_and(|min| tile.diameter(&self.polygon) < min)
+    }
+    // Vérifie si un polygone a déjà été ajouté. Un apeirogone n'ayant
+    // pas de centre fixé par ses symétries, on l'identifie par son
+    // centre idéal.
+    fn contains(&self, tile: &Tile) -> bool {
+        if self.p.is_infinite() {
+            self.ideal_tile_ids
+                .get(tile.transform.apply(DVec2::X))
+                .is_some()
+        } else {
+            self.tile_ids.get(tile.center()).is_some()
+        }
+    }
+    // Ajoute un polygone au pavage et retourne son indice.
+    fn add_tile(&mut self, tile: Tile) -> TileId {
+        let id = self.tiles.len();
+        let center = tile.center();
+        self.centers.push(center);
+        self.tile_ids.insert(center, id);
+        if self.p.is_infinite() {
+            self.ideal_tile_ids
+                .insert(tile.transform.apply(DVec2::X), id);
+        }
+        self.add_edges(&tile.vertices(&self.polygon));
+        self.tiles.push(tile);
+        id
+    }
+    // Ajoute les géodésiques formant les côtés d'un polygone, si elles
+    // n'ont pas déjà été ajoutées par un polygone voisin.
+    fn add_edges(&mut self, shape: &[DVec2]) {
+        let indices: Vec<usize> = shape
+            .iter()
+            .map(|&vertex| self.vertex_index(vertex))
+            .collect();
+        for i in 0..self.reflections.len() {
+            let j = (i + 1) % shape.len();
+            // Un côté est identifié par les indices de ses deux
+            // extrémités, dans l'ordre croissant.
+            let edge = (
+                indices[i].min(indices[j]),
+                indices[i].max(indices[j]),
+            );
+            if !self.edges.insert(edge) {
+                continue;
+            }
+            if let Some(geodesic) =
+                geodesic_passing_by_two_points(shape[i], shape[j])
+            {
+                self.geodesics.push(geodesic);
+            }
+        }
+        self.tile_vertices.push(indices);
+    }
+    // Relie les polygones partageant un côté, puis ordonne les
+    // polygones autour de chaque sommet. Tout se fait à partir des
+    // indices des sommets, sans comparaison géométrique.
+    fn link(&mut self) {
+        let vertices = self.polygon.len();
+        let sides = self.reflections.len();
+        let mut edges: HashMap<(usize, usize), Vec<(TileId, usize)>> =
+            HashMap::new();
+        let mut corners: Vec<Vec<(TileId, usize)>> =
+            vec![
+                vec![];
+                self.vertices.len() + self.ideal_vertices.len()
+            ];
+        for (id, indices) in self.tile_vertices.iter().enumerate() {
+            for i in 0..sides {
+                let j = (i + 1) % vertices;
+                let edge = (
+                    indices[i].min(indices[j]),
+                    indices[i].max(indices[j]),
+                );
+                edges.entry(edge).or_default().push((id, i));
+            }
+            for (i, &index) in indices.iter().enumerate() {
+                corners[index].push((id, i));
+            }
+        }
+        self.neighbours = vec![vec![None; sides]; self.tiles.len()];
+        for tiles in edges.values() {
+            if let &[(a, i), (b, j)] = tiles.as_slice() {
+                self.neighbours[a][i] = Some((b, j));
+                self.neighbours[b][j] = Some((a, i));
+            }
+        }
+        self.vertex_cycles = corners
+            .iter()
+            .enumerate()
+            .map(|(vertex, corners)| {
+                match (corners.first(), self.q) {
+                    (Some(&start), Order::Finite(q))
+                        if corners.len() == q as usize =>
+                    {
+                        self.vertex_cycle_from(vertex, start, q)
                     }
-                    // On appelle à nouveau avec notre polygone qui
-                    // vient d'être calculé, la fonction tile en
-                    // augmentant sa profondeur.
-                    self.tile(&next_shape, next_center, depth + 1);
-                    self.shapes.push(next_shape);
-                    self.geodesics.push(geodesic);
+                    _ => vec![],
                 }
+            })
+            .collect();
+    }
+    // Retourne les polygones rencontrés en tournant autour d'un
+    // sommet, en partant du coin d'un polygone. Le tableau est vide
+    // si l'on ne revient pas au polygone de départ après q polygones.
+    fn vertex_cycle_from(
+        &self,
+        vertex: usize,
+        (start, corner): (TileId, usize),
+        q: u8,
+    ) -> Vec<TileId> {
+        let vertices = self.polygon.len();
+        let sides = self.reflections.len();
+        let mut cycle = vec![start];
+        let (mut previous, mut current, mut corner) =
+            (None, start, corner);
+        for _ in 0..q {
+            // Les deux côtés issus du sommet sont celui qui le
+            // commence et celui qui le termine, s'ils existent (un
+            // apeirogone n'est pas fermé). On traverse celui qui ne
+            // mène pas au polygone précédent.
+            let next = [corner, (corner + vertices - 1) % vertices]
+                .into_iter()
+                .filter(|&edge| edge < sides)
+                .filter_map(|edge| self.neighbours[current][edge])
+                .find(|&(tile, _)| Some(tile) != previous);
+            let next = match next {
+                Some((tile, _)) => tile,
+                None => return vec![],
+            };
+            if next == start {
+                return cycle;
+            }
+            corner = match self.tile_vertices[next]
+                .iter()
+                .position(|&index| index == vertex)
+            {
+                Some(corner) => corner,
+                None => return vec![],
+            };
+            cycle.push(next);
+            (previous, current) = (Some(current), next);
+        }
+        vec![]
+    }
+    // Retourne les voisins d'un polygone. Le i-ème élément est le
+    // polygone situé de l'autre côté de son i-ème côté (reliant ses
+    // i-ème et (i+1)-ème sommets), accompagné de l'indice de ce côté
+    // chez le voisin. Il ne vaut rien si le voisin n'a pas été
+    // calculé.
+    pub fn neighbours(
+        &self,
+        id: TileId,
+    ) -> Option<&Vec<Option<(TileId, usize)>>> {
+        self.neighbours.get(id)
+    }
+    // Retourne le mot d'un polygone dans les réflexions par rapport aux
+    // côtés : l'isométrie du polygone est la composée, dans l'ordre du
+    // mot, des réflexions par rapport aux côtés d'indices donnés, chaque
+    // indice étant relatif au polygone atteint par les lettres
+    // précédentes. Les polygones étant parcourus couche par couche et
+    // leurs côtés dans l'ordre, ce mot est le plus petit dans l'ordre
+    // shortlex (longueur puis ordre lexicographique) parmi les mots
+    // menant au polygone. Il est donc unique et de longueur la couche.
+    // Les polygones du pavage dual, hormis le premier, n'en ont pas.
+    pub fn word(&self, id: TileId) -> Option<Vec<usize>> {
+        let layer = self.tiles.get(id)?.layer.into();
+        let mut word = Vec::with_capacity(layer);
+        let mut id = id;
+        while let Tile {
+            parent: Some(parent),
+            edge: Some(edge),
+            ..
+        } = self.tiles[id]
+        {
+            word.push(edge);
+            id = parent;
+        }
+        if id != 0 || word.len() != layer {
+            return None;
+        }
+        word.reverse();
+        Some(word)
+    }
+    // Retourne le polygone atteint en suivant un mot quelconque depuis
+    // le polygone initial, s'il a été calculé. Deux mots menant au
+    // même polygone donnent le même indice.
+    pub fn tile_from_word(&self, word: &[usize]) -> Option<TileId> {
+        if self.tiles.is_empty() {
+            return None;
+        }
+        let mut id = 0;
+        for &edge in word {
+            (id, _) = (*self.neighbours.get(id)?.get(edge)?)?;
+        }
+        Some(id)
+    }
+    // Retourne les indices des sommets d'un polygone, dans l'ordre de
+    // Tile::vertices. Deux polygones partageant un sommet lui
+    // attribuent le même indice.
+    pub fn vertex_ids(&self, id: TileId) -> Option<&Vec<usize>> {
+        self.tile_vertices.get(id)
+    }
+    // Retourne les q polygones entourant un sommet, dans l'ordre, si
+    // ils ont tous été calculés.
+    pub fn vertex_cycle(
+        &self,
+        vertex: usize,
+    ) -> Option<&Vec<TileId>> {
+        self.vertex_cycles
+            .get(vertex)
+            .filter(|cycle| !cycle.is_empty())
+    }
+    // Retourne l'indice d'un sommet, en lui en attribuant un nouveau
+    // s'il n'a encore jamais été rencontré.
+    fn vertex_index(&mut self, vertex: DVec2) -> usize {
+        // Les sommets idéaux sont identifiés par leur angle.
+        if self.q.is_infinite() {
+            if let Some(&index) = self.ideal_vertices.get(vertex) {
+                return index;
             }
+            let index = self.ideal_vertices.len();
+            self.ideal_vertices.insert(vertex, index);
+            return index;
+        }
+        if let Some(&index) = self.vertices.get(vertex) {
+            return index;
         }
+        let index = self.vertices.len();
+        self.vertices.insert(vertex, index);
+        index
+    }
+    // Retourne la distance hyperbolique entre deux points du disque.
+    pub fn distance(&self, u: DVec2, v: DVec2) -> f64 {
+        hyperbolic_distance(u, v)
+    }
+    // Retourne l'aire hyperbolique d'un polygone, soit
+    // (p - 2)π - 2pπ/q. Les polygones étant tous isométriques, cette
+    // grandeur et les suivantes ne dépendent que de p et q, et peuvent
+    // être infinies si p ou q l'est. On ne retourne rien si l'on ne
+    // peut pas paver.
+    pub fn tile_area(&self) -> Option<f64> {
+        if !self.is_tilable() {
+            return None;
+        }
+        let p = match self.p {
+            Order::Finite(p) => p as f64,
+            Order::Infinite => return Some(f64::INFINITY),
+        };
+        Some((p - 2f64) * PI - 2f64 * p * angle(self.q))
+    }
+    // Retourne le rayon hyperbolique du cercle inscrit dans un
+    // polygone, soit la distance de son centre au milieu d'un côté.
+    pub fn inradius(&self) -> Option<f64> {
+        if !self.is_tilable() {
+            return None;
+        }
+        let (p, q) = (angle(self.p), angle(self.q));
+        Some((q.cos() / p.sin()).acosh())
+    }
+    // Retourne le rayon hyperbolique du cercle circonscrit à un
+    // polygone, soit la distance de son centre à un sommet.
+    pub fn circumradius(&self) -> Option<f64> {
+        if !self.is_tilable() {
+            return None;
+        }
+        let (p, q) = (angle(self.p), angle(self.q));
+        Some((1f64 / (p.tan() * q.tan())).acosh())
+    }
+    // Retourne la longueur hyperbolique d'un côté.
+    pub fn edge_length(&self) -> Option<f64> {
+        if !self.is_tilable() {
+            return None;
+        }
+        let (p, q) = (angle(self.p), angle(self.q));
+        Some(2f64 * (p.cos() / q.sin()).acosh())
     }
     // Vérifie si l'on peut paver avec nos valeur de p et q
     pub fn is_tilable(&self) -> bool {
-        ((self.p as i16) - 2) * ((self.q as i16) - 2) > 4
+        match (self.p, self.q) {
+            (Order::Infinite, Order::Infinite) => false,
+            (Order::Infinite, Order::Finite(n))
+            | (Order::Finite(n), Order::Infinite) => n >= 3,
+            (Order::Finite(p), Order::Finite(q)) => {
+                is_hyperbolic(p, q, 2)
+            }
+        }
     }
-    pub fn p(&self) -> u8 {
+    pub fn p(&self) -> Order {
         self.p
     }
-    pub fn q(&self) -> u8 {
+    pub fn q(&self) -> Order {
         self.q
     }
     pub fn max_depth(&self) -> u8 {
         self.max_depth
     }
-    pub fn set_p(&mut self, p: u8) {
+    pub fn set_p(&mut self, p: impl Into<Order>) {
+        let p = p.into();
         if self.p != p {
             self.p = p;
+            self.automaton = None;
             self.computed = false;
         }
     }
-    pub fn set_q(&mut self, q: u8) {
+    pub fn set_q(&mut self, q: impl Into<Order>) {
+        let q = q.into();
         if self.q != q {
             self.q = q;
+            self.automaton = None;
             self.computed = false;
         }
     }
+    pub fn min_diameter(&self) -> Option<f64> {
+        self.min_diameter
+    }
+    pub fn max_radius(&self) -> Option<f64> {
+        self.max_radius
+    }
+    pub fn max_tiles(&self) -> Option<usize> {
+        self.max_tiles
+    }
+    pub fn apeirogon_edges(&self) -> usize {
+        self.apeirogon_edges
+    }
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
     pub fn set_max_depth(&mut self, max_depth: u8) {
         if self.max_depth != max_depth {
             self.max_depth = max_depth;
             self.computed = false;
         }
     }
+    // Les polygones dont le diamètre euclidien est inférieur à cette
+    // valeur ne sont pas calculés. Pour les apeirogones, elle vaut
+    // 1/200 par défaut.
+    pub fn set_min_diameter(&mut self, min_diameter: Option<f64>) {
+        if self.min_diameter != min_diameter {
+            self.min_diameter = min_diameter;
+            self.computed = false;
+        }
+    }
+    // Les polygones dont le centre est situé à une distance
+    // euclidienne de l'origine supérieure à cette valeur ne sont pas
+    // calculés.
+    pub fn set_max_radius(&mut self, max_radius: Option<f64>) {
+        if self.max_radius != max_radius {
+            self.max_radius = max_radius;
+            self.computed = false;
+        }
+    }
+    // Le calcul s'arrête dès que le pavage compte ce nombre de
+    // polygones.
+    pub fn set_max_tiles(&mut self, max_tiles: Option<usize>) {
+        if self.max_tiles != max_tiles {
+            self.max_tiles = max_tiles;
+            self.computed = false;
+        }
+    }
+    // Nombre de côtés d'un apeirogone calculés de part et d'autre de
+    // son sommet central, lorsque p est infini.
+    pub fn set_apeirogon_edges(&mut self, apeirogon_edges: usize) {
+        if self.apeirogon_edges != apeirogon_edges {
+            self.apeirogon_edges = apeirogon_edges;
+            self.computed = false;
+        }
+    }
+    pub fn set_backend(&mut self, backend: Backend) {
+        if self.backend != backend {
+            self.backend = backend;
+            self.computed = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::hyperbolic_midpoint;
+
+    // Retourne le nombre de polygones de chaque couche d'un pavage.
+    fn layer_counts(p: u8, q: u8, max_depth: u8) -> Vec<usize> {
+        let mut tiling = Tiling::new(p, q, max_depth);
+        tiling.compute();
+        (0..=max_depth)
+            .map(|layer| tiling.layer(layer).len())
+            .collect()
+    }
+
+    // Les couches suivent la série de croissance connue du pavage, ce
+    // qui vérifie que la tolérance de PointMap n'identifie pas deux
+    // polygones distincts, ni n'en sépare deux égaux.
+    #[test]
+    fn layer_counts_match_growth_series() {
+        assert_eq!(
+            layer_counts(7, 3, 7),
+            [1, 7, 21, 56, 147, 385, 1008, 2639]
+        );
+        assert_eq!(
+            layer_counts(5, 4, 7),
+            [1, 5, 15, 40, 105, 275, 720, 1885]
+        );
+        assert_eq!(
+            layer_counts(6, 4, 6),
+            [1, 6, 24, 90, 336, 1254, 4680]
+        );
+        assert_eq!(
+            layer_counts(3, 8, 7),
+            [1, 3, 6, 12, 21, 36, 63, 108]
+        );
+    }
+
+    // Un ordre nul ne permet pas de paver, seul Order::Infinite rend p
+    // ou q infini.
+    #[test]
+    fn only_infinite_order_is_ideal() {
+        assert!(!Tiling::new(7, 0, 3).is_tilable());
+        assert!(!Tiling::new(0, 3, 3).is_tilable());
+        assert!(Tiling::new(7, Order::Infinite, 3).is_tilable());
+        assert!(Tiling::new(Order::Infinite, 3, 3).is_tilable());
+        assert!(!Tiling::new(Order::Infinite, Order::Infinite, 3)
+            .is_tilable());
+    }
+
+    // Le nombre d'apeirogones calculés reste borné, quelle que soit
+    // la profondeur.
+    #[test]
+    fn apeirogons_are_bounded() {
+        let mut tiling = Tiling::new(Order::Infinite, 3, 5);
+        tiling.compute();
+        assert!(tiling.tiles().unwrap().len() < 1000);
+        tiling.set_max_depth(20);
+        tiling.compute();
+        assert!(tiling.tiles().unwrap().len() < 1000);
+    }
+
+    // Chaque condition d'arrêt est respectée par tous les polygones
+    // calculés, avec l'un ou l'autre des moteurs, et limite bien le
+    // calcul : sans elle, la même profondeur donne plus de polygones.
+    #[test]
+    fn stopping_conditions_are_respected() {
+        for backend in [Backend::Geometric, Backend::Automaton] {
+            let mut tiling = Tiling::new(7, 3, 8);
+            tiling.set_backend(backend);
+            tiling.compute();
+            let unbounded = tiling.tiles().unwrap().len();
+
+            tiling.set_max_tiles(Some(100));
+            tiling.compute();
+            assert_eq!(tiling.tiles().unwrap().len(), 100);
+            tiling.set_max_tiles(None);
+
+            tiling.set_max_radius(Some(0.8));
+            tiling.compute();
+            let tiles = tiling.tiles().unwrap();
+            assert!(tiles.len() < unbounded);
+            assert!(tiles
+                .iter()
+                .all(|tile| tile.center().length() <= 0.8));
+            tiling.set_max_radius(None);
+
+            tiling.set_min_diameter(Some(0.05));
+            tiling.compute();
+            let polygon = tiling.polygon().unwrap();
+            let tiles = tiling.tiles().unwrap();
+            assert!(tiles.len() < unbounded);
+            assert!(tiles
+                .iter()
+                .all(|tile| tile.diameter(polygon) >= 0.05));
+        }
+    }
+
+    // L'aire d'un polygone vaut (p - 2)π - 2pπ/q, et ses rayons et la
+    // longueur de ses côtés sont ceux mesurés sur le polygone initial.
+    #[test]
+    fn measures_match_polygon() {
+        for (p, q) in [(7, 3), (5, 4), (4, 6), (3, 8)] {
+            let mut tiling = Tiling::new(p, q, 2);
+            tiling.compute();
+            let polygon = tiling.polygon().unwrap().clone();
+            let (p, q) = (p as f64, q as f64);
+            let area = (p - 2f64) * PI - p * PI * 2f64 / q;
+            assert!(
+                (tiling.tile_area().unwrap() - area).abs() < 1e-9
+            );
+            for tile in tiling.tiles().unwrap() {
+                assert!((tile.area(&polygon) - area).abs() < 1e-6);
+            }
+            let circumradius =
+                hyperbolic_distance(DVec2::ZERO, polygon[0]);
+            let edge_length =
+                hyperbolic_distance(polygon[0], polygon[1]);
+            let inradius = hyperbolic_distance(
+                DVec2::ZERO,
+                hyperbolic_midpoint(polygon[0], polygon[1]),
+            );
+            assert!(
+                (tiling.circumradius().unwrap() - circumradius).abs()
+                    < 1e-9
+            );
+            assert!(
+                (tiling.edge_length().unwrap() - edge_length).abs()
+                    < 1e-9
+            );
+            assert!(
+                (tiling.inradius().unwrap() - inradius).abs() < 1e-9
+            );
+        }
+    }
+
+    // Le centre d'un polygone, ainsi que tout point proche de l'un de
+    // ses sommets à l'intérieur, sont situés dans ce polygone. Les
+    // points hors du disque, ou sur son bord, ne sont dans aucun.
+    #[test]
+    fn locate_finds_tiles() {
+        for (p, q) in [(7, 3), (5, 4), (3, 8)] {
+            let mut tiling = Tiling::new(p, q, 4);
+            tiling.compute();
+            let polygon = tiling.polygon().unwrap().clone();
+            for (id, tile) in
+                tiling.tiles().unwrap().iter().enumerate()
+            {
+                assert_eq!(tiling.locate(tile.center()), Some(id));
+                let inner = tile.transform().apply(polygon[0] * 0.9);
+                assert_eq!(tiling.locate(inner), Some(id));
+            }
+            for outside in [DVec2::new(2.0, 0.0), DVec2::X, -DVec2::Y]
+            {
+                assert_eq!(tiling.locate(outside), None);
+            }
+        }
+    }
+
+    // Les relations de voisinage sont symétriques, les polygones des
+    // couches intérieures ont tous leurs voisins, et les polygones
+    // entourant un sommet le contiennent et se suivent.
+    #[test]
+    fn adjacency_is_consistent() {
+        for (p, q) in [(7, 3), (5, 4), (3, 8)] {
+            let max_depth = 4;
+            let mut tiling = Tiling::new(p, q, max_depth);
+            tiling.compute();
+            for (id, tile) in
+                tiling.tiles().unwrap().iter().enumerate()
+            {
+                let neighbours = tiling.neighbours(id).unwrap();
+                for (i, neighbour) in neighbours.iter().enumerate() {
+                    if let &Some((other, j)) = neighbour {
+                        assert_eq!(
+                            tiling.neighbours(other).unwrap()[j],
+                            Some((id, i))
+                        );
+                    }
+                }
+                if tile.layer() < max_depth {
+                    assert!(neighbours.iter().all(Option::is_some));
+                }
+            }
+            let mut cycles = 0;
+            for vertex in 0..tiling.vertices.len() {
+                let cycle = match tiling.vertex_cycle(vertex) {
+                    Some(cycle) => cycle,
+                    None => continue,
+                };
+                cycles += 1;
+                assert_eq!(cycle.len(), q as usize);
+                for (k, &id) in cycle.iter().enumerate() {
+                    assert!(tiling
+                        .vertex_ids(id)
+                        .unwrap()
+                        .contains(&vertex));
+                    let next = cycle[(k + 1) % cycle.len()];
+                    assert!(tiling
+                        .neighbours(id)
+                        .unwrap()
+                        .iter()
+                        .flatten()
+                        .any(|&(neighbour, _)| neighbour == next));
+                }
+            }
+            // Les sommets du polygone initial sont entourés de
+            // polygones calculés.
+            assert!(cycles >= p as usize);
+        }
+    }
+
+    // Les sommets duals sont les centres des polygones, et les
+    // polygones duals sont centrés sur les sommets.
+    #[test]
+    fn dual_swaps_vertices_and_centers() {
+        for (p, q) in [(7, 3), (5, 4), (4, 6)] {
+            let mut tiling = Tiling::new(p, q, 3);
+            tiling.compute();
+            let dual = tiling.dual().unwrap();
+            assert_eq!((dual.p(), dual.q()), (q.into(), p.into()));
+            let tiles = tiling.tiles().unwrap();
+            let polygon = tiling.polygon().unwrap();
+            let mut vertices =
+                vec![DVec2::ZERO; tiling.vertices.len()];
+            for (id, tile) in tiles.iter().enumerate() {
+                let positions = tile.vertices(polygon);
+                for (&vertex, position) in tiling
+                    .vertex_ids(id)
+                    .unwrap()
+                    .iter()
+                    .zip(positions)
+                {
+                    vertices[vertex] = position;
+                }
+            }
+            let dual_polygon = dual.polygon().unwrap();
+            for (id, tile) in dual.tiles().unwrap().iter().enumerate()
+            {
+                assert_eq!(tile.layer(), 0);
+                assert!(
+                    hyperbolic_distance(tile.center(), vertices[id])
+                        < 1e-6
+                );
+                let positions = tile.vertices(dual_polygon);
+                for (&vertex, position) in
+                    dual.vertex_ids(id).unwrap().iter().zip(positions)
+                {
+                    if vertex < tiles.len() {
+                        assert!(
+                            hyperbolic_distance(
+                                position,
+                                tiles[vertex].center()
+                            ) < 1e-6
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    // Si p est pair, deux polygones duals voisins ont des isométries
+    // d'orientations opposées.
+    #[test]
+    fn dual_neighbours_have_opposite_orientations() {
+        for (p, q) in [(8, 3), (6, 4), (4, 6)] {
+            let mut tiling = Tiling::new(p, q, 3);
+            tiling.compute();
+            let dual = tiling.dual().unwrap();
+            let tiles = dual.tiles().unwrap();
+            for (id, tile) in tiles.iter().enumerate() {
+                for &(neighbour, _) in
+                    dual.neighbours(id).unwrap().iter().flatten()
+                {
+                    assert_ne!(
+                        tile.transform().is_reversing(),
+                        tiles[neighbour].transform().is_reversing()
+                    );
+                }
+            }
+        }
+    }
 }
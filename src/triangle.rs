@@ -0,0 +1,447 @@
+use std::collections::{HashSet, VecDeque};
+use std::f64::consts::PI;
+
+use glam::DVec2;
+
+use crate::geometry::{geodesic_passing_by_two_points, Geodesic};
+use crate::key::PointMap;
+use crate::mobius::Mobius;
+
+// Indice d'un triangle dans le tableau retourné par
+// TriangleTiling::triangles.
+pub type TriangleId = usize;
+
+// Vérifie si le triangle d'angles π/p, π/q et π/r est hyperbolique,
+// c'est-à-dire si 1/p + 1/q + 1/r < 1.
+pub fn is_hyperbolic(p: u8, q: u8, r: u8) -> bool {
+    let (p, q, r) = (p as u32, q as u32, r as u32);
+    p > 0 && q > 0 && r > 0 && q * r + p * r + p * q < p * q * r
+}
+
+// Retourne les sommets A, B et C du triangle fondamental, d'angles
+// π/p, π/q et π/r. A est à l'origine, B sur l'axe des abscisses et C
+// sur la demi-droite d'angle π/p.
+pub fn fundamental_triangle(
+    p: u8,
+    q: u8,
+    r: u8,
+) -> Option<[DVec2; 3]> {
+    if !is_hyperbolic(p, q, r) {
+        return None;
+    }
+    let (alpha, beta, gamma) =
+        (PI / p as f64, PI / q as f64, PI / r as f64);
+    // Loi des cosinus hyperbolique : les longueurs des côtés se
+    // déduisent des angles.
+    let ab = ((gamma.cos() + alpha.cos() * beta.cos())
+        / (alpha.sin() * beta.sin()))
+    .acosh();
+    let ac = ((beta.cos() + alpha.cos() * gamma.cos())
+        / (alpha.sin() * gamma.sin()))
+    .acosh();
+    // Un point à distance hyperbolique d de l'origine est à distance
+    // euclidienne tanh(d/2).
+    Some([
+        DVec2::ZERO,
+        DVec2::X * (ab / 2f64).tanh(),
+        DVec2::new(alpha.cos(), alpha.sin()) * (ac / 2f64).tanh(),
+    ])
+}
+
+// Retourne les côtés AB, AC et BC d'un triangle dont A est à
+// l'origine.
+pub(crate) fn sides(vertices: &[DVec2; 3]) -> Option<[Geodesic; 3]> {
+    let [a, b, c] = *vertices;
+    Some([
+        geodesic_passing_by_two_points(a, b)?,
+        geodesic_passing_by_two_points(a, c)?,
+        geodesic_passing_by_two_points(b, c)?,
+    ])
+}
+
+// Retourne la bissectrice de l'angle d'un triangle en un sommet, les
+// deux autres sommets étant donnés.
+pub(crate) fn bisector(
+    vertex: DVec2,
+    u: DVec2,
+    v: DVec2,
+) -> Option<Geodesic> {
+    // On ramène le sommet à l'origine, où la bissectrice est le
+    // diamètre dirigé par la somme des directions des côtés.
+    let to_origin = Mobius::translation(vertex, DVec2::ZERO);
+    let direction = to_origin.apply(u).normalize()
+        + to_origin.apply(v).normalize();
+    geodesic_passing_by_two_points(
+        vertex,
+        to_origin.inverse().apply(direction.normalize() / 2f64),
+    )
+}
+
+// Retourne le centre du cercle inscrit d'un triangle, où se coupent
+// ses bissectrices.
+pub(crate) fn incenter(vertices: &[DVec2; 3]) -> Option<DVec2> {
+    let [a, b, c] = *vertices;
+    bisector(a, b, c)?.intersect(&bisector(b, a, c)?)
+}
+
+// Image du triangle fondamental par un élément du groupe engendré par
+// les réflexions par rapport à ses côtés. Sa couche est la longueur
+// du plus court mot en ces réflexions qui la donne.
+#[derive(Debug, Clone)]
+pub struct Triangle {
+    transform: Mobius,
+    layer: u8,
+    parent: Option<TriangleId>,
+}
+
+impl Triangle {
+    pub fn transform(&self) -> &Mobius {
+        &self.transform
+    }
+    // Retourne les sommets du triangle, à partir de ceux du triangle
+    // fondamental (voir TriangleTiling::fundamental).
+    pub fn vertices(&self, fundamental: &[DVec2; 3]) -> [DVec2; 3] {
+        fundamental.map(|vertex| self.transform.apply(vertex))
+    }
+    pub fn layer(&self) -> u8 {
+        self.layer
+    }
+    pub fn parent(&self) -> Option<TriangleId> {
+        self.parent
+    }
+    // Vérifie si le triangle est l'image du triangle fondamental par
+    // un nombre pair de réflexions. Les triangles pairs et impairs
+    // forment le damier classique.
+    pub fn is_even(&self) -> bool {
+        !self.transform.is_reversing()
+    }
+}
+
+// Pavage par les triangles d'angles π/p, π/q et π/r.
+pub struct TriangleTiling {
+    p: u8,
+    q: u8,
+    r: u8,
+    max_depth: u8,
+    min_diameter: Option<f64>,
+    max_triangles: Option<usize>,
+    fundamental: Option<[DVec2; 3]>,
+    reflections: Vec<Mobius>,
+    triangles: Vec<Triangle>,
+    geodesics: Vec<Geodesic>,
+    // Point intérieur au triangle fondamental : le centre de son
+    // cercle inscrit. N'étant sur aucun miroir, il n'est fixé par aucun
+    // élément du groupe autre que l'identité, et ses images sont
+    // éloignées d'au moins deux fois le rayon inscrit, même si le
+    // triangle est très fin : elles identifient donc les triangles
+    // malgré la tolérance de PointMap, contrairement par exemple au
+    // centre de gravité.
+    anchor: DVec2,
+    // Indice du triangle associé à chaque image de anchor.
+    triangle_ids: PointMap<TriangleId>,
+    // Indices des sommets et des côtés déjà rencontrés.
+    vertices: PointMap<usize>,
+    edges: HashSet<(usize, usize)>,
+    computed: bool,
+}
+
+impl TriangleTiling {
+    pub fn new(p: u8, q: u8, r: u8, max_depth: u8) -> Self {
+        TriangleTiling {
+            p,
+            q,
+            r,
+            max_depth,
+            min_diameter: None,
+            max_triangles: None,
+            fundamental: None,
+            reflections: vec![],
+            triangles: vec![],
+            geodesics: vec![],
+            anchor: DVec2::ZERO,
+            triangle_ids: PointMap::new(),
+            vertices: PointMap::new(),
+            edges: HashSet::new(),
+            computed: false,
+        }
+    }
+    // Retourne le triangle fondamental (voir fundamental_triangle).
+    pub fn fundamental(&self) -> Option<&[DVec2; 3]> {
+        self.fundamental.as_ref()
+    }
+    pub fn triangles(&self) -> Option<&Vec<Triangle>> {
+        // Si le tableau est vide, on ne retourne rien.
+        match self.triangles.as_slice() {
+            &[] => None,
+            _ => Some(&self.triangles),
+        }
+    }
+    pub fn geodesics(&self) -> Option<&Vec<Geodesic>> {
+        // Si le tableau est vide, on ne retourne rien.
+        match self.geodesics.as_slice() {
+            &[] => None,
+            _ => Some(&self.geodesics),
+        }
+    }
+    pub fn compute(&mut self) {
+        if self.computed {
+            return;
+        }
+        // On réinitialise nos variables.
+        self.fundamental = None;
+        self.reflections = vec![];
+        self.triangles = vec![];
+        self.geodesics = vec![];
+        self.triangle_ids = PointMap::new();
+        self.vertices = PointMap::new();
+        self.edges = HashSet::new();
+        self.computed = true;
+        let fundamental =
+            match fundamental_triangle(self.p, self.q, self.r) {
+                Some(fundamental) => fundamental,
+                None => return,
+            };
+        let sides = match sides(&fundamental) {
+            Some(sides) => sides,
+            None => return,
+        };
+        self.anchor = match incenter(&fundamental) {
+            Some(anchor) => anchor,
+            None => return,
+        };
+        self.reflections =
+            sides.iter().map(|side| side.reflection()).collect();
+        self.fundamental = Some(fundamental);
+        self.add_triangle(Triangle {
+            transform: Mobius::identity(),
+            layer: 0,
+            parent: None,
+        });
+        self.tile();
+    }
+    // Calcule les triangles couche par couche, comme Tiling::tile.
+    fn tile(&mut self) {
+        let anchor = self.anchor;
+        let mut queue = VecDeque::from([0]);
+        while let Some(current) = queue.pop_front() {
+            let current_triangle = self.triangles[current].clone();
+            if current_triangle.layer >= self.max_depth {
+                continue;
+            }
+            for i in 0..self.reflections.len() {
+                if self
+                    .max_triangles
+                    .is_some_and(|max| self.triangles.len() >= max)
+                {
+                    return;
+                }
+                let next_triangle = Triangle {
+                    transform: current_triangle
+                        .transform
+                        .compose(&self.reflections[i]),
+                    layer: current_triangle.layer + 1,
+                    parent: Some(current),
+                };
+                let next_anchor =
+                    next_triangle.transform.apply(anchor);
+                if self.triangle_ids.get(next_anchor).is_some() {
+                    continue;
+                }
+                if self.min_diameter.is_some_and(|min| {
+                    self.diameter(&next_triangle) < min
+                }) {
+                    continue;
+                }
+                queue.push_back(self.add_triangle(next_triangle));
+            }
+        }
+    }
+    // Retourne le diamètre euclidien d'un triangle.
+    fn diameter(&self, triangle: &Triangle) -> f64 {
+        let [a, b, c] = match &self.fundamental {
+            Some(fundamental) => triangle.vertices(fundamental),
+            None => return 0f64,
+        };
+        a.distance(b).max(a.distance(c)).max(b.distance(c))
+    }
+    // Ajoute un triangle et ses côtés, et retourne son indice.
+    fn add_triangle(&mut self, triangle: Triangle) -> TriangleId {
+        let id = self.triangles.len();
+        self.triangle_ids
+            .insert(triangle.transform.apply(self.anchor), id);
+        if let Some(fundamental) = &self.fundamental {
+            let vertices = triangle.vertices(fundamental);
+            let indices =
+                vertices.map(|vertex| self.vertex_index(vertex));
+            for (i, j) in [(0, 1), (0, 2), (1, 2)] {
+                let edge = (
+                    indices[i].min(indices[j]),
+                    indices[i].max(indices[j]),
+                );
+                if !self.edges.insert(edge) {
+                    continue;
+                }
+                if let Some(geodesic) = geodesic_passing_by_two_points(
+                    vertices[i],
+                    vertices[j],
+                ) {
+                    self.geodesics.push(geodesic);
+                }
+            }
+        }
+        self.triangles.push(triangle);
+        id
+    }
+    // Retourne l'indice d'un sommet, en lui en attribuant un nouveau
+    // s'il n'a encore jamais été rencontré.
+    fn vertex_index(&mut self, vertex: DVec2) -> usize {
+        if let Some(&index) = self.vertices.get(vertex) {
+            return index;
+        }
+        let index = self.vertices.len();
+        self.vertices.insert(vertex, index);
+        index
+    }
+    pub fn is_tilable(&self) -> bool {
+        is_hyperbolic(self.p, self.q, self.r)
+    }
+    pub fn p(&self) -> u8 {
+        self.p
+    }
+    pub fn q(&self) -> u8 {
+        self.q
+    }
+    pub fn r(&self) -> u8 {
+        self.r
+    }
+    pub fn max_depth(&self) -> u8 {
+        self.max_depth
+    }
+    pub fn min_diameter(&self) -> Option<f64> {
+        self.min_diameter
+    }
+    pub fn max_triangles(&self) -> Option<usize> {
+        self.max_triangles
+    }
+    pub fn set_p(&mut self, p: u8) {
+        if self.p != p {
+            self.p = p;
+            self.computed = false;
+        }
+    }
+    pub fn set_q(&mut self, q: u8) {
+        if self.q != q {
+            self.q = q;
+            self.computed = false;
+        }
+    }
+    pub fn set_r(&mut self, r: u8) {
+        if self.r != r {
+            self.r = r;
+            self.computed = false;
+        }
+    }
+    pub fn set_max_depth(&mut self, max_depth: u8) {
+        if self.max_depth != max_depth {
+            self.max_depth = max_depth;
+            self.computed = false;
+        }
+    }
+    // Les triangles dont le diamètre euclidien est inférieur à cette
+    // valeur ne sont pas calculés.
+    pub fn set_min_diameter(&mut self, min_diameter: Option<f64>) {
+        if self.min_diameter != min_diameter {
+            self.min_diameter = min_diameter;
+            self.computed = false;
+        }
+    }
+    // Le calcul s'arrête dès que le pavage compte ce nombre de
+    // triangles.
+    pub fn set_max_triangles(
+        &mut self,
+        max_triangles: Option<usize>,
+    ) {
+        if self.max_triangles != max_triangles {
+            self.max_triangles = max_triangles;
+            self.computed = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    // Les couches du pavage (2,3,7) suivent sa série de croissance.
+    #[test]
+    fn layer_counts_match_growth_series() {
+        let max_depth = 8;
+        let mut tiling = TriangleTiling::new(2, 3, 7, max_depth);
+        tiling.compute();
+        let mut counts = vec![0; max_depth as usize + 1];
+        for triangle in tiling.triangles().unwrap() {
+            counts[triangle.layer() as usize] += 1;
+        }
+        assert_eq!(counts, [1, 3, 5, 7, 9, 12, 16, 20, 24]);
+    }
+
+    // Même très fin, le triangle fondamental a des images distinctes
+    // de part et d'autre de chacun de ses côtés : les premières
+    // couches ne dépendent pas de p.
+    #[test]
+    fn thin_triangles_are_kept() {
+        for p in [7, 50, 80, 100, 200] {
+            let mut tiling = TriangleTiling::new(p, 3, 3, 1);
+            tiling.compute();
+            assert_eq!(tiling.triangles().unwrap().len(), 4);
+            tiling.set_max_depth(3);
+            tiling.compute();
+            assert_eq!(tiling.triangles().unwrap().len(), 20);
+        }
+    }
+
+    // Deux triangles partageant un côté sont l'image l'un de l'autre
+    // par une réflexion : l'un est pair, l'autre impair.
+    #[test]
+    fn neighbours_alternate_parity() {
+        for (p, q, r) in [(2, 3, 7), (2, 4, 5), (3, 3, 4)] {
+            let mut tiling = TriangleTiling::new(p, q, r, 6);
+            tiling.compute();
+            let fundamental = *tiling.fundamental().unwrap();
+            let mut edges: HashMap<(usize, usize), Vec<bool>> =
+                HashMap::new();
+            for triangle in tiling.triangles().unwrap() {
+                let indices =
+                    triangle.vertices(&fundamental).map(|vertex| {
+                        *tiling.vertices.get(vertex).unwrap()
+                    });
+                for (i, j) in [(0, 1), (0, 2), (1, 2)] {
+                    let edge = (
+                        indices[i].min(indices[j]),
+                        indices[i].max(indices[j]),
+                    );
+                    edges
+                        .entry(edge)
+                        .or_default()
+                        .push(triangle.is_even());
+                }
+            }
+            let mut shared = 0;
+            for parities in edges.values() {
+                match parities.as_slice() {
+                    [a, b] => {
+                        assert_ne!(a, b);
+                        shared += 1;
+                    }
+                    [_] => {}
+                    _ => panic!(
+                        "côté partagé par plus de deux triangles"
+                    ),
+                }
+            }
+            assert!(shared > 0);
+        }
+    }
+}
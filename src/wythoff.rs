@@ -0,0 +1,525 @@
+use std::collections::HashSet;
+use std::f64::consts::PI;
+
+use glam::DVec2;
+
+use crate::geometry::{
+    geodesic_passing_by_two_points, hyperbolic_distance, Geodesic,
+};
+use crate::mobius::Mobius;
+use crate::render::*;
+use crate::tiling::{Order, Tiling};
+use crate::triangle::{
+    bisector, fundamental_triangle, incenter, sides,
+};
+
+// Distance hyperbolique en dessous de laquelle deux sommets d'une
+// face sont confondus.
+const TOLERANCE: f64 = 1e-9;
+// Nombre maximal d'itérations de la méthode de Newton utilisée pour
+// le pavage adouci.
+const MAX_SNUB_STEPS: usize = 100;
+
+// Pavages uniformes obtenus par la construction de Wythoff. Le
+// triangle fondamental a pour sommets le centre P du polygone
+// initial, l'un de ses sommets Q et le milieu R de l'un de ses
+// côtés. Ses angles valent π/p en P, π/q en Q et π/2 en R. Le
+// générateur est un point de ce triangle dont on calcule les images
+// par les réflexions du groupe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Variant {
+    // {p,q} : générateur en Q.
+    Regular,
+    // t{p,q} : générateur sur QR, à égale distance de PQ et PR.
+    Truncated,
+    // r{p,q} : générateur en R.
+    Rectified,
+    // t{q,p} : générateur sur PR, à égale distance de PQ et QR.
+    Bitruncated,
+    // {q,p} : générateur en P.
+    Dual,
+    // rr{p,q} : générateur sur PQ, à égale distance de PR et QR.
+    Cantellated,
+    // tr{p,q} : générateur à égale distance des trois côtés.
+    Omnitruncated,
+    // sr{p,q} : générateur dont les images par les rotations du
+    // groupe forment des triangles équilatéraux.
+    Snub,
+}
+
+// Ce sur quoi une face est centrée dans le pavage {p,q} sous-jacent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FaceType {
+    // Le centre d'un polygone.
+    Center,
+    // Un sommet.
+    Vertex,
+    // Le milieu d'un côté.
+    Edge,
+    // Les triangles propres au pavage adouci.
+    Snub,
+}
+
+#[derive(Debug, Clone)]
+pub struct Face {
+    face_type: FaceType,
+    vertices: Vec<DVec2>,
+}
+
+impl Face {
+    pub fn face_type(&self) -> FaceType {
+        self.face_type
+    }
+    pub fn vertices(&self) -> &Vec<DVec2> {
+        &self.vertices
+    }
+    // Retourne les géodésiques formant les côtés de la face.
+    pub fn geodesics(&self) -> Vec<Geodesic> {
+        let n = self.vertices.len();
+        (0..n)
+            .filter_map(|i| {
+                geodesic_passing_by_two_points(
+                    self.vertices[i],
+                    self.vertices[(i + 1) % n],
+                )
+            })
+            .collect()
+    }
+}
+
+impl Render for Face {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        for geodesic in self.geodesics() {
+            geodesic.render(renderer);
+        }
+    }
+}
+
+// Triangle fondamental et réflexions par rapport à ses côtés : a
+// porte PQ, b porte PR et c porte QR.
+struct Kaleidoscope {
+    p: DVec2,
+    q: DVec2,
+    r: DVec2,
+    a: Mobius,
+    b: Mobius,
+    c: Mobius,
+}
+
+impl Kaleidoscope {
+    fn new(p: u8, q: u8) -> Option<Self> {
+        // Le triangle fondamental de Wythoff est celui du groupe
+        // (p,q,2) : B est un sommet du polygone initial et C le
+        // milieu de son premier côté.
+        let vertices = fundamental_triangle(p, q, 2)?;
+        let [a, b, c] =
+            sides(&vertices)?.map(|side| side.reflection());
+        Some(Kaleidoscope {
+            p: vertices[0],
+            q: vertices[1],
+            r: vertices[2],
+            a,
+            b,
+            c,
+        })
+    }
+    fn sides(&self) -> Option<[Geodesic; 3]> {
+        sides(&[self.p, self.q, self.r])
+    }
+    // Retourne le générateur de la variante.
+    fn generator(&self, variant: Variant) -> Option<DVec2> {
+        let (p, q, r) = (self.p, self.q, self.r);
+        match variant {
+            Variant::Regular => Some(q),
+            Variant::Rectified => Some(r),
+            Variant::Dual => Some(p),
+            Variant::Truncated => {
+                self.sides()?[2].intersect(&bisector(p, q, r)?)
+            }
+            Variant::Bitruncated => {
+                self.sides()?[1].intersect(&bisector(q, p, r)?)
+            }
+            Variant::Cantellated => {
+                self.sides()?[0].intersect(&bisector(r, p, q)?)
+            }
+            Variant::Omnitruncated => incenter(&[p, q, r]),
+            Variant::Snub => self.snub_generator(),
+        }
+    }
+    // Retourne les écarts entre les longueurs des côtés des
+    // triangles du pavage adouci ayant un générateur donné.
+    fn snub_residual(&self, point: DVec2) -> DVec2 {
+        let ab = self.a.compose(&self.b).apply(point);
+        let ac = self.a.compose(&self.c).apply(point);
+        let bc = self.b.compose(&self.c).apply(point);
+        let length = hyperbolic_distance(point, ab);
+        DVec2::new(
+            length - hyperbolic_distance(point, ac),
+            length - hyperbolic_distance(point, bc),
+        )
+    }
+    // Calcule le générateur du pavage adouci par la méthode de
+    // Newton, en partant du centre du cercle inscrit.
+    fn snub_generator(&self) -> Option<DVec2> {
+        let mut point = self.generator(Variant::Omnitruncated)?;
+        let h = 1e-7;
+        for _ in 0..MAX_SNUB_STEPS {
+            let residual = self.snub_residual(point);
+            if residual.length() < 1e-13 {
+                break;
+            }
+            // On estime la jacobienne par différences finies.
+            let dx = (self.snub_residual(point + DVec2::X * h)
+                - residual)
+                / h;
+            let dy = (self.snub_residual(point + DVec2::Y * h)
+                - residual)
+                / h;
+            let determinant = dx.perp_dot(dy);
+            if determinant == 0f64 {
+                return None;
+            }
+            point -= DVec2::new(
+                residual.perp_dot(dy),
+                dx.perp_dot(residual),
+            ) / determinant;
+        }
+        Some(point).filter(|point| point.length() < 1f64)
+    }
+}
+
+// Trie des points selon leur angle autour d'un centre, en supprimant
+// les doublons. Le résultat est une face de la variante, ou moins de
+// trois points si elle est dégénérée.
+fn sort_around(center: DVec2, points: Vec<DVec2>) -> Vec<DVec2> {
+    let to_origin = Mobius::translation(center, DVec2::ZERO);
+    let mut points: Vec<(f64, DVec2)> = points
+        .into_iter()
+        .map(|point| {
+            let image = to_origin.apply(point);
+            (image.y.atan2(image.x), point)
+        })
+        .collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut face: Vec<DVec2> = vec![];
+    for (_, point) in points {
+        if face.iter().all(|&other| {
+            hyperbolic_distance(point, other) > TOLERANCE
+        }) {
+            face.push(point);
+        }
+    }
+    face
+}
+
+// Pavage uniforme calculé à partir d'un pavage régulier {p,q}.
+pub struct Wythoff {
+    tiling: Tiling,
+    variant: Variant,
+    generator: Option<DVec2>,
+    faces: Vec<Face>,
+    computed: bool,
+}
+
+impl Wythoff {
+    pub fn new(
+        p: u8,
+        q: u8,
+        max_depth: u8,
+        variant: Variant,
+    ) -> Self {
+        Wythoff {
+            tiling: Tiling::new(p, q, max_depth),
+            variant,
+            generator: None,
+            faces: vec![],
+            computed: false,
+        }
+    }
+    // Retourne le pavage régulier sous-jacent, afin de pouvoir en
+    // modifier les conditions d'arrêt.
+    pub fn tiling(&self) -> &Tiling {
+        &self.tiling
+    }
+    pub fn tiling_mut(&mut self) -> &mut Tiling {
+        self.computed = false;
+        &mut self.tiling
+    }
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+    pub fn set_variant(&mut self, variant: Variant) {
+        if self.variant != variant {
+            self.variant = variant;
+            self.computed = false;
+        }
+    }
+    // Retourne le générateur, situé dans le triangle fondamental.
+    pub fn generator(&self) -> Option<DVec2> {
+        self.generator
+    }
+    pub fn faces(&self) -> Option<&Vec<Face>> {
+        // Si le tableau est vide, on ne retourne rien.
+        match self.faces.as_slice() {
+            &[] => None,
+            _ => Some(&self.faces),
+        }
+    }
+    // Retourne les faces d'un type donné.
+    pub fn faces_of_type(&self, face_type: FaceType) -> Vec<&Face> {
+        self.faces
+            .iter()
+            .filter(|face| face.face_type == face_type)
+            .collect()
+    }
+    pub fn compute(&mut self) {
+        if self.computed {
+            return;
+        }
+        self.generator = None;
+        self.faces = vec![];
+        self.computed = true;
+        self.tiling.compute();
+        // La construction n'a de sens que si p et q sont finis.
+        let (p, q) = match (self.tiling.p(), self.tiling.q()) {
+            (Order::Finite(p), Order::Finite(q)) => (p, q),
+            _ => return,
+        };
+        let kaleidoscope = match Kaleidoscope::new(p, q) {
+            Some(kaleidoscope) => kaleidoscope,
+            None => return,
+        };
+        let generator = match kaleidoscope.generator(self.variant) {
+            Some(generator) => generator,
+            None => return,
+        };
+        self.generator = Some(generator);
+        self.add_faces(&kaleidoscope, generator, p.into(), q.into());
+    }
+    // Calcule les faces autour des centres, des sommets et des
+    // milieux des côtés du pavage régulier, puis les triangles du
+    // pavage adouci.
+    fn add_faces(
+        &mut self,
+        kaleidoscope: &Kaleidoscope,
+        generator: DVec2,
+        p: usize,
+        q: usize,
+    ) {
+        let snub = self.variant == Variant::Snub;
+        let rotation = Mobius::rotation(2f64 * PI / p as f64);
+        // Les points de départ des orbites : le pavage adouci
+        // n'utilise que les rotations du groupe.
+        let seeds = |reflection: &Mobius| {
+            if snub {
+                vec![generator]
+            } else {
+                vec![generator, reflection.apply(generator)]
+            }
+        };
+        // Faces locales, dans le repère du polygone initial.
+        let mut center_face = vec![];
+        let mut step = Mobius::identity();
+        for _ in 0..p {
+            for seed in seeds(&kaleidoscope.a) {
+                center_face.push(step.apply(seed));
+            }
+            step = rotation.compose(&step);
+        }
+        let center_face = sort_around(kaleidoscope.p, center_face);
+        let vertex_rotation = kaleidoscope.c.compose(&kaleidoscope.a);
+        let mut vertex_face = vec![];
+        let mut step = Mobius::identity();
+        for _ in 0..q {
+            for seed in seeds(&kaleidoscope.a) {
+                vertex_face.push(step.apply(seed));
+            }
+            step = vertex_rotation.compose(&step);
+        }
+        let vertex_face = sort_around(kaleidoscope.q, vertex_face);
+        let edge_face = if snub {
+            vec![]
+        } else {
+            sort_around(
+                kaleidoscope.r,
+                vec![
+                    generator,
+                    kaleidoscope.b.apply(generator),
+                    kaleidoscope.c.apply(generator),
+                    kaleidoscope
+                        .b
+                        .compose(&kaleidoscope.c)
+                        .apply(generator),
+                ],
+            )
+        };
+        let snub_face = if snub {
+            vec![
+                generator,
+                kaleidoscope
+                    .a
+                    .compose(&kaleidoscope.b)
+                    .apply(generator),
+                kaleidoscope
+                    .a
+                    .compose(&kaleidoscope.c)
+                    .apply(generator),
+            ]
+        } else {
+            vec![]
+        };
+
+        // On place chaque face locale sur les polygones, sommets et
+        // côtés du pavage. Pour le pavage adouci, les isométries qui
+        // renversent l'orientation sont composées avec la réflexion a,
+        // qui conserve P et Q.
+        let tiles = match self.tiling.tiles() {
+            Some(tiles) => tiles.clone(),
+            None => return,
+        };
+        let even = |transform: Mobius| {
+            if snub && transform.is_reversing() {
+                transform.compose(&kaleidoscope.a)
+            } else {
+                transform
+            }
+        };
+        let mut vertices = HashSet::new();
+        let mut edges = HashSet::new();
+        for (id, tile) in tiles.iter().enumerate() {
+            let transform = even(*tile.transform());
+            self.push_face(
+                FaceType::Center,
+                &transform,
+                &center_face,
+            );
+            let ids = match self.tiling.vertex_ids(id) {
+                Some(ids) => ids.clone(),
+                None => continue,
+            };
+            let mut step = Mobius::identity();
+            for i in 0..p {
+                let local = even(tile.transform().compose(&step));
+                if vertices.insert(ids[i]) {
+                    self.push_face(
+                        FaceType::Vertex,
+                        &local,
+                        &vertex_face,
+                    );
+                }
+                let edge = (
+                    ids[i].min(ids[(i + 1) % p]),
+                    ids[i].max(ids[(i + 1) % p]),
+                );
+                if edges.insert(edge) {
+                    self.push_face(
+                        FaceType::Edge,
+                        &local,
+                        &edge_face,
+                    );
+                }
+                // Chaque polygone contient p des triangles du pavage
+                // adouci.
+                self.push_face(
+                    FaceType::Snub,
+                    &transform.compose(&step),
+                    &snub_face,
+                );
+                step = rotation.compose(&step);
+            }
+        }
+    }
+    // Ajoute l'image d'une face locale, si elle n'est pas dégénérée.
+    fn push_face(
+        &mut self,
+        face_type: FaceType,
+        transform: &Mobius,
+        face: &[DVec2],
+    ) {
+        if face.len() < 3 {
+            return;
+        }
+        self.faces.push(Face {
+            face_type,
+            vertices: face
+                .iter()
+                .map(|&vertex| transform.apply(vertex))
+                .collect(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Vérifie que toutes les arêtes des pavages uniformes dérivés de
+    // {p,q} ont la même longueur, et que chaque type de face a le
+    // nombre de côtés attendu. Les faces dégénérées ne sont pas
+    // calculées.
+    fn faces_are_uniform_for(p: usize, q: usize) {
+        let cases = [
+            (Variant::Regular, [p, 0, 0, 0]),
+            (Variant::Truncated, [2 * p, q, 0, 0]),
+            (Variant::Rectified, [p, q, 0, 0]),
+            (Variant::Bitruncated, [p, 2 * q, 0, 0]),
+            (Variant::Dual, [0, q, 0, 0]),
+            (Variant::Cantellated, [p, q, 4, 0]),
+            (Variant::Omnitruncated, [2 * p, 2 * q, 4, 0]),
+            (Variant::Snub, [p, q, 0, 3]),
+        ];
+        let face_types = [
+            FaceType::Center,
+            FaceType::Vertex,
+            FaceType::Edge,
+            FaceType::Snub,
+        ];
+        for (variant, sizes) in cases {
+            let mut wythoff =
+                Wythoff::new(p as u8, q as u8, 3, variant);
+            wythoff.compute();
+            for (face_type, size) in face_types.into_iter().zip(sizes)
+            {
+                let faces = wythoff.faces_of_type(face_type);
+                assert_eq!(
+                    faces.is_empty(),
+                    size == 0,
+                    "{variant:?}"
+                );
+                for face in faces {
+                    assert_eq!(
+                        face.vertices().len(),
+                        size,
+                        "{variant:?}"
+                    );
+                }
+            }
+            let lengths: Vec<f64> = wythoff
+                .faces()
+                .unwrap()
+                .iter()
+                .flat_map(|face| {
+                    let vertices = face.vertices();
+                    let n = vertices.len();
+                    (0..n).map(move |i| {
+                        hyperbolic_distance(
+                            vertices[i],
+                            vertices[(i + 1) % n],
+                        )
+                    })
+                })
+                .collect();
+            let min =
+                lengths.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = lengths.iter().copied().fold(0f64, f64::max);
+            assert!(max - min < 1e-6, "{variant:?} : {min} à {max}");
+        }
+    }
+
+    #[test]
+    fn faces_are_uniform() {
+        for (p, q) in [(7, 3), (5, 4), (4, 6)] {
+            faces_are_uniform_for(p, q);
+        }
+    }
+}